@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Support code for the macros in this crate.
+//!
+//! Items in this module are not a part of the public API and
+//! may be changed without notice.
+
+#[cfg(feature = "env-ceiling")]
+pub mod ceiling;
+#[cfg(all(
+    any(feature = "cpuid", feature = "verify-static"),
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+pub mod cpuid;
+pub mod enable;
+pub mod features;
+pub mod implications;
+#[cfg(feature = "mask")]
+pub mod mask;
+#[cfg(feature = "proof-tokens")]
+pub mod tokens;
+#[cfg(all(
+    feature = "verify-static",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+pub mod verify;
+#[cfg(all(
+    feature = "vlen",
+    any(
+        target_arch = "riscv32",
+        target_arch = "riscv64",
+        target_arch = "aarch64",
+        target_arch = "arm64ec"
+    )
+))]
+pub mod vlen;
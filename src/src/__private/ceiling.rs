@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Ceiling of detected features on filling per-site caches
+//! (with the `env-ceiling` feature).
+//!
+//! Without the `env-ceiling` feature, this module is not compiled and
+//! the macros expand to the bare feature detection.
+
+use crate::ceiling::disabled;
+
+/// Returns whether the feature is not disabled by the environment variable.
+#[inline]
+pub fn is_allowed(feature: &str) -> bool {
+    !disabled().iter().any(|f| f == feature)
+}
@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Feature detection on x86 by CPUID (and XGETBV) without the standard library.
+//!
+//! Unlike the feature detection macros in the standard library, features
+//! enabled on compilation are not assumed to be available.
+//! Results of CPUID are cached in atomic variables.
+
+#![allow(unsafe_code)]
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86 as arch;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64 as arch;
+
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// Index of the CPUID (or XCR0) word.
+#[derive(Clone, Copy)]
+enum Word {
+    /// CPUID leaf 1: ECX.
+    L1Ecx,
+    /// CPUID leaf 1: EDX.
+    L1Edx,
+    /// CPUID leaf 7 (subleaf 0): EBX.
+    L7Ebx,
+    /// CPUID leaf 7 (subleaf 0): ECX.
+    L7Ecx,
+    /// CPUID leaf 7 (subleaf 0): EDX.
+    L7Edx,
+    /// CPUID leaf 7 (subleaf 1): EAX.
+    L7S1Eax,
+    /// CPUID leaf 7 (subleaf 1): EDX.
+    L7S1Edx,
+    /// CPUID leaf 0xD (subleaf 1): EAX.
+    LDS1Eax,
+    /// CPUID leaf 0x8000_0001: ECX.
+    E1Ecx,
+    /// XCR0 (lower 32 bits; zero if not enabled by the OS).
+    Xcr0,
+}
+
+/// The number of [`Word`]s.
+const NUM_WORDS: usize = Word::Xcr0 as usize + 1;
+
+/// OS-enabled state (XCR0) required by AVX features (SSE and AVX states).
+const XCR0_AVX: u32 = 0x0000_0006;
+/// OS-enabled state (XCR0) required by AVX-512 features
+/// (SSE, AVX, opmask and ZMM states).
+const XCR0_AVX512: u32 = 0x0000_00e6;
+
+/// Known features: the name, the CPUID bit and the required XCR0 state.
+const FEATURES: &[(&str, Word, u32, u32)] = &[
+    ("adx", Word::L7Ebx, 19, 0),
+    ("aes", Word::L1Ecx, 25, 0),
+    ("avx", Word::L1Ecx, 28, XCR0_AVX),
+    ("avx2", Word::L7Ebx, 5, XCR0_AVX),
+    ("avx512bf16", Word::L7S1Eax, 5, XCR0_AVX512),
+    ("avx512bitalg", Word::L7Ecx, 12, XCR0_AVX512),
+    ("avx512bw", Word::L7Ebx, 30, XCR0_AVX512),
+    ("avx512cd", Word::L7Ebx, 28, XCR0_AVX512),
+    ("avx512dq", Word::L7Ebx, 17, XCR0_AVX512),
+    ("avx512f", Word::L7Ebx, 16, XCR0_AVX512),
+    ("avx512fp16", Word::L7Edx, 23, XCR0_AVX512),
+    ("avx512ifma", Word::L7Ebx, 21, XCR0_AVX512),
+    ("avx512vbmi", Word::L7Ecx, 1, XCR0_AVX512),
+    ("avx512vbmi2", Word::L7Ecx, 6, XCR0_AVX512),
+    ("avx512vl", Word::L7Ebx, 31, XCR0_AVX512),
+    ("avx512vnni", Word::L7Ecx, 11, XCR0_AVX512),
+    ("avx512vp2intersect", Word::L7Edx, 8, XCR0_AVX512),
+    ("avx512vpopcntdq", Word::L7Ecx, 14, XCR0_AVX512),
+    ("avxifma", Word::L7S1Eax, 23, XCR0_AVX),
+    ("avxneconvert", Word::L7S1Edx, 5, XCR0_AVX),
+    ("avxvnni", Word::L7S1Eax, 4, XCR0_AVX),
+    ("avxvnniint16", Word::L7S1Edx, 10, XCR0_AVX),
+    ("avxvnniint8", Word::L7S1Edx, 4, XCR0_AVX),
+    ("bmi1", Word::L7Ebx, 3, 0),
+    ("bmi2", Word::L7Ebx, 8, 0),
+    ("cmpxchg16b", Word::L1Ecx, 13, 0),
+    ("ermsb", Word::L7Ebx, 9, 0),
+    ("f16c", Word::L1Ecx, 29, XCR0_AVX),
+    ("fma", Word::L1Ecx, 12, XCR0_AVX),
+    ("fxsr", Word::L1Edx, 24, 0),
+    ("gfni", Word::L7Ecx, 8, 0),
+    ("lzcnt", Word::E1Ecx, 5, 0),
+    ("movbe", Word::L1Ecx, 22, 0),
+    ("pclmulqdq", Word::L1Ecx, 1, 0),
+    ("popcnt", Word::L1Ecx, 23, 0),
+    ("rdrand", Word::L1Ecx, 30, 0),
+    ("rdseed", Word::L7Ebx, 18, 0),
+    ("rtm", Word::L7Ebx, 11, 0),
+    ("sha", Word::L7Ebx, 29, 0),
+    ("sha512", Word::L7S1Eax, 0, XCR0_AVX),
+    ("sm3", Word::L7S1Eax, 1, XCR0_AVX),
+    ("sm4", Word::L7S1Eax, 2, XCR0_AVX),
+    ("sse", Word::L1Edx, 25, 0),
+    ("sse2", Word::L1Edx, 26, 0),
+    ("sse3", Word::L1Ecx, 0, 0),
+    ("sse4.1", Word::L1Ecx, 19, 0),
+    ("sse4.2", Word::L1Ecx, 20, 0),
+    ("sse4a", Word::E1Ecx, 6, 0),
+    ("ssse3", Word::L1Ecx, 9, 0),
+    ("tbm", Word::E1Ecx, 21, 0),
+    ("vaes", Word::L7Ecx, 9, XCR0_AVX),
+    ("vpclmulqdq", Word::L7Ecx, 10, XCR0_AVX),
+    ("xsave", Word::L1Ecx, 26, 0),
+    ("xsavec", Word::LDS1Eax, 1, 0),
+    ("xsaveopt", Word::LDS1Eax, 0, 0),
+    ("xsaves", Word::LDS1Eax, 3, 0),
+];
+
+/// Cached CPUID (and XCR0) words.
+static WORDS: [AtomicU32; NUM_WORDS] = [const { AtomicU32::new(0) }; NUM_WORDS];
+/// Whether [`WORDS`] is initialized.
+static READY: AtomicBool = AtomicBool::new(false);
+
+/// Executes CPUID.
+#[allow(unused_unsafe)]
+fn cpuid(leaf: u32, subleaf: u32) -> arch::CpuidResult {
+    // SAFETY: CPUID is checked to be available (always on x86_64).
+    unsafe { arch::__cpuid_count(leaf, subleaf) }
+}
+
+/// Reads XCR0 (lower 32 bits).
+#[target_feature(enable = "xsave")]
+unsafe fn xgetbv0() -> u32 {
+    // SAFETY: the caller checks OSXSAVE (XGETBV is enabled by the OS).
+    unsafe { arch::_xgetbv(0) as u32 }
+}
+
+/// Queries CPUID words (all zero if CPUID is not available).
+fn query() -> [u32; NUM_WORDS] {
+    let mut words = [0; NUM_WORDS];
+    #[cfg(target_arch = "x86")]
+    if !arch::has_cpuid() {
+        return words;
+    }
+    let max = cpuid(0, 0).eax;
+    if max >= 1 {
+        let leaf = cpuid(1, 0);
+        words[Word::L1Ecx as usize] = leaf.ecx;
+        words[Word::L1Edx as usize] = leaf.edx;
+        // OSXSAVE
+        if leaf.ecx & (1 << 27) != 0 {
+            // SAFETY: XGETBV is enabled by the OS (which requires XSAVE).
+            words[Word::Xcr0 as usize] = unsafe { xgetbv0() };
+        }
+    }
+    if max >= 7 {
+        let leaf = cpuid(7, 0);
+        words[Word::L7Ebx as usize] = leaf.ebx;
+        words[Word::L7Ecx as usize] = leaf.ecx;
+        words[Word::L7Edx as usize] = leaf.edx;
+        if leaf.eax >= 1 {
+            let leaf = cpuid(7, 1);
+            words[Word::L7S1Eax as usize] = leaf.eax;
+            words[Word::L7S1Edx as usize] = leaf.edx;
+        }
+    }
+    if max >= 0xd {
+        words[Word::LDS1Eax as usize] = cpuid(0xd, 1).eax;
+    }
+    if cpuid(0x8000_0000, 0).eax >= 0x8000_0001 {
+        words[Word::E1Ecx as usize] = cpuid(0x8000_0001, 0).ecx;
+    }
+    words
+}
+
+/// Returns the cached CPUID word (querying on the first call).
+fn word(index: Word) -> u32 {
+    if !READY.load(Ordering::Acquire) {
+        // Racing initializations store the same values.
+        for (cache, value) in WORDS.iter().zip(query()) {
+            cache.store(value, Ordering::Relaxed);
+        }
+        READY.store(true, Ordering::Release);
+    }
+    WORDS[index as usize].load(Ordering::Relaxed)
+}
+
+/// Checks whether the feature can be detected by this module.
+///
+/// # Panics
+///
+/// Panics (causes a compilation error in the constant context) if the feature
+/// is unknown to this module.
+#[cfg(feature = "cpuid")]
+pub const fn check(feature: &str) {
+    let mut i = 0;
+    while i < FEATURES.len() {
+        if super::features::str_eq(FEATURES[i].0, feature) {
+            return;
+        }
+        i += 1;
+    }
+    let msg = super::features::Message::new()
+        .push("target feature \"")
+        .push(feature)
+        .push("\" cannot be detected by `dynamic(core)` and `cached(core)` (unknown to CPUID detection)");
+    panic!("{}", msg.as_str());
+}
+
+/// Returns whether the feature is available on the running CPU
+/// (or [`None`] if the feature is unknown to this module).
+pub fn is_available(feature: &str) -> Option<bool> {
+    let &(_, index, bit, xcr0) = FEATURES.iter().find(|&&(name, ..)| name == feature)?;
+    Some(word(index) & (1 << bit) != 0 && word(Word::Xcr0) & xcr0 == xcr0)
+}
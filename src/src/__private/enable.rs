@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Support for the `enable_features` option.
+
+/// The value of a body compiled with features enabled.
+///
+/// The body is wrapped in a closure returning this type so that
+/// `return` expressions and the `?` operator in the body (which would
+/// only leave the closure) cause compilation errors.
+pub struct Body<R>(pub R);
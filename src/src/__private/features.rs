@@ -0,0 +1,657 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Compile-time validation of feature names.
+//!
+//! Each table contains target features known to either the Rust compiler
+//! (`target_feature`) or the feature detection macro in the standard library.
+//! Validation is performed in the constant context so that an unknown feature
+//! causes a compilation error with suggestions.
+
+/// Known target features: x86 (32-bit and 64-bit).
+pub const X86: &[&str] = &[
+    "abm",
+    "adx",
+    "aes",
+    "amx-avx512",
+    "amx-bf16",
+    "amx-complex",
+    "amx-fp16",
+    "amx-fp8",
+    "amx-int8",
+    "amx-movrs",
+    "amx-tf32",
+    "amx-tile",
+    "apxf",
+    "avx",
+    "avx10.1",
+    "avx10.2",
+    "avx2",
+    "avx512bf16",
+    "avx512bitalg",
+    "avx512bw",
+    "avx512cd",
+    "avx512dq",
+    "avx512f",
+    "avx512fp16",
+    "avx512ifma",
+    "avx512vbmi",
+    "avx512vbmi2",
+    "avx512vl",
+    "avx512vnni",
+    "avx512vp2intersect",
+    "avx512vpopcntdq",
+    "avxifma",
+    "avxneconvert",
+    "avxvnni",
+    "avxvnniint16",
+    "avxvnniint8",
+    "bmi1",
+    "bmi2",
+    "cmpxchg16b",
+    "ermsb",
+    "f16c",
+    "fma",
+    "fxsr",
+    "gfni",
+    "kl",
+    "lahfsahf",
+    "lzcnt",
+    "mmx",
+    "movbe",
+    "movrs",
+    "pclmulqdq",
+    "popcnt",
+    "prfchw",
+    "rdrand",
+    "rdseed",
+    "rtm",
+    "sha",
+    "sha512",
+    "sm3",
+    "sm4",
+    "sse",
+    "sse2",
+    "sse3",
+    "sse4.1",
+    "sse4.2",
+    "sse4a",
+    "ssse3",
+    "tbm",
+    "tsc",
+    "vaes",
+    "vpclmulqdq",
+    "widekl",
+    "x87",
+    "xop",
+    "xsave",
+    "xsavec",
+    "xsaveopt",
+    "xsaves",
+];
+
+/// Known target features: AArch64 (including Arm64EC).
+pub const AARCH64: &[&str] = &[
+    "aes",
+    "asimd",
+    "bf16",
+    "bti",
+    "crc",
+    "cssc",
+    "dit",
+    "dotprod",
+    "dpb",
+    "dpb2",
+    "ecv",
+    "f32mm",
+    "f64mm",
+    "faminmax",
+    "fcma",
+    "fhm",
+    "flagm",
+    "flagm2",
+    "fp",
+    "fp16",
+    "fp8",
+    "fp8dot2",
+    "fp8dot4",
+    "fp8fma",
+    "fpmr",
+    "frintts",
+    "hbc",
+    "i8mm",
+    "jsconv",
+    "lor",
+    "lse",
+    "lse128",
+    "lse2",
+    "lut",
+    "mops",
+    "mte",
+    "neon",
+    "outline-atomics",
+    "paca",
+    "pacg",
+    "pan",
+    "pauth-lr",
+    "pmull",
+    "pmuv3",
+    "rand",
+    "ras",
+    "rcpc",
+    "rcpc2",
+    "rcpc3",
+    "rdm",
+    "sb",
+    "sha2",
+    "sha3",
+    "sm4",
+    "sme",
+    "sme-b16b16",
+    "sme-f16f16",
+    "sme-f64f64",
+    "sme-f8f16",
+    "sme-f8f32",
+    "sme-fa64",
+    "sme-i16i64",
+    "sme-lutv2",
+    "sme2",
+    "sme2p1",
+    "spe",
+    "ssbs",
+    "ssve-fp8dot2",
+    "ssve-fp8dot4",
+    "ssve-fp8fma",
+    "sve",
+    "sve-b16b16",
+    "sve2",
+    "sve2-aes",
+    "sve2-bitperm",
+    "sve2-sha3",
+    "sve2-sm4",
+    "sve2p1",
+    "tme",
+    "v8.1a",
+    "v8.2a",
+    "v8.3a",
+    "v8.4a",
+    "v8.5a",
+    "v8.6a",
+    "v8.7a",
+    "v8.8a",
+    "v8.9a",
+    "v9.1a",
+    "v9.2a",
+    "v9.3a",
+    "v9.4a",
+    "v9.5a",
+    "v9a",
+    "vh",
+    "wfxt",
+];
+
+/// Known target features: Arm (32-bit).
+pub const ARM: &[&str] = &[
+    "aclass",
+    "aes",
+    "crc",
+    "d32",
+    "dotprod",
+    "dsp",
+    "fp-armv8",
+    "fp16",
+    "fpregs",
+    "i8mm",
+    "mclass",
+    "neon",
+    "pmull",
+    "rclass",
+    "sha2",
+    "soft-float",
+    "thumb-mode",
+    "thumb2",
+    "trustzone",
+    "v5te",
+    "v6",
+    "v6k",
+    "v6t2",
+    "v7",
+    "v8",
+    "vfp2",
+    "vfp3",
+    "vfp4",
+    "virtualization",
+];
+
+/// Known target features: RISC-V (32-bit and 64-bit).
+pub const RISCV: &[&str] = &[
+    "a",
+    "b",
+    "c",
+    "d",
+    "e",
+    "f",
+    "j",
+    "m",
+    "p",
+    "q",
+    "relax",
+    "rv128i",
+    "rv32e",
+    "rv32i",
+    "rv64i",
+    "rva23u64",
+    "supm",
+    "unaligned-scalar-mem",
+    "unaligned-vector-mem",
+    "v",
+    "za128rs",
+    "za64rs",
+    "zaamo",
+    "zabha",
+    "zacas",
+    "zalrsc",
+    "zam",
+    "zama16b",
+    "zawrs",
+    "zba",
+    "zbb",
+    "zbc",
+    "zbkb",
+    "zbkc",
+    "zbkx",
+    "zbs",
+    "zca",
+    "zcb",
+    "zcd",
+    "zcf",
+    "zcmop",
+    "zdinx",
+    "zfa",
+    "zfbfmin",
+    "zfh",
+    "zfhmin",
+    "zfinx",
+    "zhinx",
+    "zhinxmin",
+    "zic64b",
+    "zicbom",
+    "zicbop",
+    "zicboz",
+    "ziccamoa",
+    "ziccif",
+    "zicclsm",
+    "ziccrse",
+    "zicntr",
+    "zicond",
+    "zicsr",
+    "zifencei",
+    "zihintntl",
+    "zihintpause",
+    "zihpm",
+    "zimop",
+    "zk",
+    "zkn",
+    "zknd",
+    "zkne",
+    "zknh",
+    "zkr",
+    "zks",
+    "zksed",
+    "zksh",
+    "zkt",
+    "ztso",
+    "zvbb",
+    "zvbc",
+    "zve32f",
+    "zve32x",
+    "zve64d",
+    "zve64f",
+    "zve64x",
+    "zvfbfmin",
+    "zvfbfwma",
+    "zvfh",
+    "zvfhmin",
+    "zvkb",
+    "zvkg",
+    "zvkn",
+    "zvknc",
+    "zvkned",
+    "zvkng",
+    "zvknha",
+    "zvknhb",
+    "zvks",
+    "zvksc",
+    "zvksed",
+    "zvksg",
+    "zvksh",
+    "zvkt",
+    "zvl1024b",
+    "zvl128b",
+    "zvl16384b",
+    "zvl2048b",
+    "zvl256b",
+    "zvl32768b",
+    "zvl32b",
+    "zvl4096b",
+    "zvl512b",
+    "zvl64b",
+    "zvl65536b",
+    "zvl8192b",
+];
+
+/// Known target features: PowerPC (32-bit and 64-bit).
+pub const POWERPC: &[&str] = &[
+    "altivec",
+    "msync",
+    "partword-atomics",
+    "power10-vector",
+    "power8",
+    "power8-altivec",
+    "power8-crypto",
+    "power8-vector",
+    "power9",
+    "power9-altivec",
+    "power9-vector",
+    "quadword-atomics",
+    "vsx",
+];
+
+/// Known target features: MIPS (32-bit and 64-bit).
+pub const MIPS: &[&str] = &["fp64", "msa", "virt"];
+
+/// Known target features: LoongArch (32-bit and 64-bit).
+pub const LOONGARCH: &[&str] = &[
+    "32s",
+    "d",
+    "div32",
+    "f",
+    "frecipe",
+    "lam-bh",
+    "lamcas",
+    "lasx",
+    "lbt",
+    "ld-seq-sa",
+    "lsx",
+    "lvz",
+    "relax",
+    "scq",
+    "ual",
+];
+
+/// Known target features: s390x.
+pub const S390X: &[&str] = &[
+    "backchain",
+    "concurrent-functions",
+    "deflate-conversion",
+    "enhanced-sort",
+    "guarded-storage",
+    "high-word",
+    "message-security-assist-extension12",
+    "message-security-assist-extension3",
+    "message-security-assist-extension4",
+    "message-security-assist-extension5",
+    "message-security-assist-extension8",
+    "message-security-assist-extension9",
+    "miscellaneous-extensions-2",
+    "miscellaneous-extensions-3",
+    "miscellaneous-extensions-4",
+    "nnp-assist",
+    "transactional-execution",
+    "vector",
+    "vector-enhancements-1",
+    "vector-enhancements-2",
+    "vector-enhancements-3",
+    "vector-packed-decimal",
+    "vector-packed-decimal-enhancement",
+    "vector-packed-decimal-enhancement-2",
+    "vector-packed-decimal-enhancement-3",
+];
+
+/// Known target features: SPARC (32-bit and 64-bit).
+pub const SPARC: &[&str] = &["leoncasa", "v8plus", "v9"];
+
+/// Known target features: WebAssembly (32-bit and 64-bit).
+pub const WASM: &[&str] = &[
+    "atomics",
+    "bulk-memory",
+    "exception-handling",
+    "extended-const",
+    "gc",
+    "multivalue",
+    "mutable-globals",
+    "nontrapping-fptoint",
+    "reference-types",
+    "relaxed-simd",
+    "sign-ext",
+    "simd128",
+    "tail-call",
+    "wide-arithmetic",
+];
+
+/// Maximum length of an error message (longer messages are truncated).
+const MESSAGE_LEN: usize = 512;
+
+/// Maximum length of feature names to compute the edit distance.
+const NAME_LEN: usize = 64;
+
+/// Maximum number of suggestions.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Error message built in the constant context.
+pub(super) struct Message {
+    /// Message buffer.
+    buf: [u8; MESSAGE_LEN],
+    /// Length of the message.
+    len: usize,
+}
+
+impl Message {
+    /// Creates an empty message.
+    pub(super) const fn new() -> Self {
+        Self {
+            buf: [0; MESSAGE_LEN],
+            len: 0,
+        }
+    }
+
+    /// Appends a string (only if it fits in the buffer as a whole).
+    pub(super) const fn push(mut self, s: &str) -> Self {
+        let bytes = s.as_bytes();
+        if self.len + bytes.len() <= MESSAGE_LEN {
+            let mut i = 0;
+            while i < bytes.len() {
+                self.buf[self.len + i] = bytes[i];
+                i += 1;
+            }
+            self.len += bytes.len();
+        }
+        self
+    }
+
+    /// Returns the message as a string.
+    pub(super) const fn as_str(&self) -> &str {
+        match core::str::from_utf8(self.buf.split_at(self.len).0) {
+            Ok(s) => s,
+            Err(_) => "",
+        }
+    }
+}
+
+/// Returns whether two strings are equal.
+pub(super) const fn str_eq(a: &str, b: &str) -> bool {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Returns whether the list contains the name.
+const fn contains(list: &[&str], name: &str) -> bool {
+    let mut i = 0;
+    while i < list.len() {
+        if str_eq(list[i], name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Returns the edit (Levenshtein) distance between two names.
+///
+/// Substituting a character with the same one but in a different case
+/// costs nothing.
+const fn distance(a: &str, b: &str) -> usize {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    let a_len = if a.len() < NAME_LEN {
+        a.len()
+    } else {
+        NAME_LEN
+    };
+    let b_len = if b.len() < NAME_LEN {
+        b.len()
+    } else {
+        NAME_LEN
+    };
+    let mut prev = [0usize; NAME_LEN + 1];
+    let mut curr = [0usize; NAME_LEN + 1];
+    let mut j = 0;
+    while j <= b_len {
+        prev[j] = j;
+        j += 1;
+    }
+    let mut i = 1;
+    while i <= a_len {
+        curr[0] = i;
+        let mut j = 1;
+        while j <= b_len {
+            let cost = if a[i - 1].eq_ignore_ascii_case(&b[j - 1]) {
+                0
+            } else {
+                1
+            };
+            let mut d = prev[j - 1] + cost;
+            if prev[j] + 1 < d {
+                d = prev[j] + 1;
+            }
+            if curr[j - 1] + 1 < d {
+                d = curr[j - 1] + 1;
+            }
+            curr[j] = d;
+            j += 1;
+        }
+        prev = curr;
+        i += 1;
+    }
+    prev[b_len]
+}
+
+/// Checks whether the feature is known on one of given architectures.
+///
+/// `archs` contains the tables per architecture.  If an architecture has no
+/// tables (i.e. the architecture is not known to this crate),
+/// validation is skipped.
+///
+/// # Panics
+///
+/// Panics (causes a compilation error in the constant context) if the feature
+/// is unknown.  The message contains the closest known names, if any.
+pub const fn check(arch: &str, archs: &[&[&[&str]]], feature: &str) {
+    // Search the feature.
+    let mut a = 0;
+    while a < archs.len() {
+        if archs[a].is_empty() {
+            return;
+        }
+        let mut t = 0;
+        while t < archs[a].len() {
+            if contains(archs[a][t], feature) {
+                return;
+            }
+            t += 1;
+        }
+        a += 1;
+    }
+    // Compute the minimum edit distance.
+    let mut min = usize::MAX;
+    let mut a = 0;
+    while a < archs.len() {
+        let mut t = 0;
+        while t < archs[a].len() {
+            let table = archs[a][t];
+            let mut i = 0;
+            while i < table.len() {
+                let d = distance(table[i], feature);
+                if d < min {
+                    min = d;
+                }
+                i += 1;
+            }
+            t += 1;
+        }
+        a += 1;
+    }
+    let mut msg = Message::new()
+        .push("unknown target feature \"")
+        .push(feature)
+        .push("\" on ")
+        .push(arch);
+    // Suggest names with the minimum distance (only if close enough).
+    let threshold = if feature.len() / 3 > 2 {
+        feature.len() / 3
+    } else {
+        2
+    };
+    if min <= threshold {
+        let mut suggested: [&str; MAX_SUGGESTIONS] = [""; MAX_SUGGESTIONS];
+        let mut count = 0;
+        let mut a = 0;
+        while a < archs.len() {
+            let mut t = 0;
+            while t < archs[a].len() {
+                let table = archs[a][t];
+                let mut i = 0;
+                while i < table.len() {
+                    if count < MAX_SUGGESTIONS
+                        && distance(table[i], feature) == min
+                        && !contains(suggested.split_at(count).0, table[i])
+                    {
+                        suggested[count] = table[i];
+                        count += 1;
+                    }
+                    i += 1;
+                }
+                t += 1;
+            }
+            a += 1;
+        }
+        let mut i = 0;
+        while i < count {
+            msg = msg
+                .push(if i == 0 {
+                    "; did you mean "
+                } else if i + 1 == count {
+                    " or "
+                } else {
+                    ", "
+                })
+                .push("\"")
+                .push(suggested[i])
+                .push("\"");
+            i += 1;
+        }
+        msg = msg.push("?");
+    }
+    panic!("{}", msg.as_str());
+}
@@ -0,0 +1,428 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Compile-time detection of unreachable feature-specific clauses.
+//!
+//! Each table contains direct implications of target features
+//! (e.g. `"avx2"` implies `"avx"`).  Implications are followed transitively.
+//!
+//! A condition is represented in the disjunctive normal form:
+//! a list of terms (operands of `||`), each term is a list of factors
+//! (operands of `&&`) and each factor is a list of features (a parenthesized
+//! conjunction such as a named level may contain multiple features).
+//! An empty factor or an empty condition means that the condition
+//! cannot be analyzed.
+
+use super::features::str_eq;
+
+/// Direct implications of target features (a feature and implied features).
+pub type Implications = &'static [(&'static str, &'static [&'static str])];
+
+/// Implications of target features: x86 (32-bit and 64-bit).
+pub const X86: Implications = &[
+    ("aes", &["sse2"]),
+    ("amx-avx512", &["amx-tile"]),
+    ("amx-bf16", &["amx-tile"]),
+    ("amx-complex", &["amx-tile"]),
+    ("amx-fp16", &["amx-tile"]),
+    ("amx-fp8", &["amx-tile"]),
+    ("amx-int8", &["amx-tile"]),
+    ("amx-movrs", &["amx-tile"]),
+    ("amx-tf32", &["amx-tile"]),
+    ("avx", &["sse4.2"]),
+    (
+        "avx10.1",
+        &[
+            "avx512bf16",
+            "avx512bitalg",
+            "avx512bw",
+            "avx512cd",
+            "avx512dq",
+            "avx512f",
+            "avx512fp16",
+            "avx512ifma",
+            "avx512vbmi",
+            "avx512vbmi2",
+            "avx512vl",
+            "avx512vnni",
+            "avx512vpopcntdq",
+        ],
+    ),
+    ("avx10.2", &["avx10.1"]),
+    ("avx2", &["avx"]),
+    ("avx512bf16", &["avx512bw"]),
+    ("avx512bitalg", &["avx512bw"]),
+    ("avx512bw", &["avx512f"]),
+    ("avx512cd", &["avx512f"]),
+    ("avx512dq", &["avx512f"]),
+    ("avx512f", &["avx2", "f16c", "fma"]),
+    ("avx512fp16", &["avx512bw", "avx512dq", "avx512vl"]),
+    ("avx512ifma", &["avx512f"]),
+    ("avx512vbmi", &["avx512bw"]),
+    ("avx512vbmi2", &["avx512bw"]),
+    ("avx512vl", &["avx512f"]),
+    ("avx512vnni", &["avx512f"]),
+    ("avx512vp2intersect", &["avx512f"]),
+    ("avx512vpopcntdq", &["avx512f"]),
+    ("avxifma", &["avx2"]),
+    ("avxneconvert", &["avx2"]),
+    ("avxvnni", &["avx2"]),
+    ("avxvnniint16", &["avx2"]),
+    ("avxvnniint8", &["avx2"]),
+    ("f16c", &["avx"]),
+    ("fma", &["avx"]),
+    ("gfni", &["sse2"]),
+    ("kl", &["sse2"]),
+    ("pclmulqdq", &["sse2"]),
+    ("sha", &["sse2"]),
+    ("sha512", &["avx2"]),
+    ("sm3", &["avx"]),
+    ("sm4", &["avx2"]),
+    ("sse2", &["sse"]),
+    ("sse3", &["sse2"]),
+    ("sse4.1", &["ssse3"]),
+    ("sse4.2", &["sse4.1"]),
+    ("sse4a", &["sse3"]),
+    ("ssse3", &["sse3"]),
+    ("vaes", &["aes", "avx2"]),
+    ("vpclmulqdq", &["avx", "pclmulqdq"]),
+    ("widekl", &["kl"]),
+    ("xop", &["avx", "sse4a"]),
+    ("xsavec", &["xsave"]),
+    ("xsaveopt", &["xsave"]),
+    ("xsaves", &["xsave"]),
+];
+
+/// Implications of target features: AArch64 (including Arm64EC).
+pub const AARCH64: Implications = &[
+    ("aes", &["neon"]),
+    ("dotprod", &["neon"]),
+    ("dpb2", &["dpb"]),
+    ("f32mm", &["sve"]),
+    ("f64mm", &["sve"]),
+    ("fcma", &["neon"]),
+    ("fhm", &["fp16"]),
+    ("flagm2", &["flagm"]),
+    ("fp16", &["neon"]),
+    ("fp8", &["bf16", "faminmax", "lut"]),
+    ("fp8dot2", &["fp8dot4"]),
+    ("fp8dot4", &["fp8fma"]),
+    ("fp8fma", &["fp8"]),
+    ("jsconv", &["neon"]),
+    ("lse128", &["lse"]),
+    ("rcpc2", &["rcpc"]),
+    ("rcpc3", &["rcpc2"]),
+    ("rdm", &["neon"]),
+    ("sha2", &["neon"]),
+    ("sha3", &["sha2"]),
+    ("sm4", &["neon"]),
+    ("sme", &["bf16"]),
+    ("sme-b16b16", &["bf16", "sme2", "sve-b16b16"]),
+    ("sme-f16f16", &["sme2"]),
+    ("sme-f64f64", &["sme"]),
+    ("sme-f8f16", &["sme-f8f32"]),
+    ("sme-f8f32", &["fp8", "sme2"]),
+    ("sme-fa64", &["sme", "sve2"]),
+    ("sme-i16i64", &["sme"]),
+    ("sme2", &["sme"]),
+    ("sme2p1", &["sme2"]),
+    ("ssve-fp8dot2", &["ssve-fp8dot4"]),
+    ("ssve-fp8dot4", &["ssve-fp8fma"]),
+    ("ssve-fp8fma", &["fp8", "sme2"]),
+    ("sve", &["neon"]),
+    ("sve-b16b16", &["bf16"]),
+    ("sve2", &["sve"]),
+    ("sve2-aes", &["aes", "sve2"]),
+    ("sve2-bitperm", &["sve2"]),
+    ("sve2-sha3", &["sha3", "sve2"]),
+    ("sve2-sm4", &["sm4", "sve2"]),
+    ("sve2p1", &["sve2"]),
+    ("v8.1a", &["crc", "lor", "lse", "pan", "rdm", "vh"]),
+    ("v8.2a", &["dpb", "ras", "v8.1a"]),
+    ("v8.3a", &["jsconv", "paca", "pacg", "rcpc", "v8.2a"]),
+    ("v8.4a", &["dit", "dotprod", "flagm", "v8.3a"]),
+    ("v8.5a", &["bti", "dpb2", "sb", "ssbs", "v8.4a"]),
+    ("v8.6a", &["bf16", "i8mm", "v8.5a"]),
+    ("v8.7a", &["v8.6a", "wfxt"]),
+    ("v8.8a", &["hbc", "mops", "v8.7a"]),
+    ("v8.9a", &["cssc", "v8.8a"]),
+    ("v9.1a", &["v8.6a", "v9a"]),
+    ("v9.2a", &["v8.7a", "v9.1a"]),
+    ("v9.3a", &["v8.8a", "v9.2a"]),
+    ("v9.4a", &["v8.9a", "v9.3a"]),
+    ("v9.5a", &["v9.4a"]),
+    ("v9a", &["sve2", "v8.5a"]),
+];
+
+/// Implications of target features: Arm (32-bit).
+pub const ARM: Implications = &[
+    ("aes", &["neon"]),
+    ("dotprod", &["neon"]),
+    ("fp-armv8", &["vfp4"]),
+    ("i8mm", &["neon"]),
+    ("neon", &["vfp3"]),
+    ("sha2", &["neon"]),
+    ("v6", &["v5te"]),
+    ("v6k", &["v6"]),
+    ("v6t2", &["thumb2", "v6k"]),
+    ("v7", &["v6t2"]),
+    ("v8", &["v7"]),
+    ("vfp3", &["vfp2"]),
+    ("vfp4", &["vfp3"]),
+];
+
+/// Implications of target features: RISC-V (32-bit and 64-bit).
+pub const RISCV: Implications = &[
+    ("a", &["zaamo", "zalrsc"]),
+    ("b", &["zba", "zbb", "zbs"]),
+    ("c", &["zca"]),
+    ("d", &["f"]),
+    ("f", &["zicsr"]),
+    ("q", &["d"]),
+    ("v", &["zve64d", "zvl128b"]),
+    ("za64rs", &["za128rs"]),
+    ("zabha", &["zaamo"]),
+    ("zacas", &["zaamo"]),
+    ("zcb", &["zca"]),
+    ("zcd", &["d", "zca"]),
+    ("zcf", &["f", "zca"]),
+    ("zcmop", &["zca"]),
+    ("zdinx", &["zfinx"]),
+    ("zfa", &["f"]),
+    ("zfbfmin", &["f"]),
+    ("zfh", &["zfhmin"]),
+    ("zfhmin", &["f"]),
+    ("zfinx", &["zicsr"]),
+    ("zhinx", &["zhinxmin"]),
+    ("zhinxmin", &["zfinx"]),
+    ("zicntr", &["zicsr"]),
+    ("zihpm", &["zicsr"]),
+    ("zk", &["zkn", "zkr", "zkt"]),
+    ("zkn", &["zbkb", "zbkc", "zbkx", "zknd", "zkne", "zknh"]),
+    ("zks", &["zbkb", "zbkc", "zbkx", "zksed", "zksh"]),
+    ("zvbb", &["zvkb"]),
+    ("zvbc", &["zve64x"]),
+    ("zve32f", &["f", "zve32x"]),
+    ("zve32x", &["zicsr", "zvl32b"]),
+    ("zve64d", &["d", "zve64f"]),
+    ("zve64f", &["zve32f", "zve64x"]),
+    ("zve64x", &["zve32x", "zvl64b"]),
+    ("zvfbfmin", &["zve32f"]),
+    ("zvfbfwma", &["zfbfmin", "zvfbfmin"]),
+    ("zvfh", &["zfhmin", "zvfhmin"]),
+    ("zvfhmin", &["zve32f"]),
+    ("zvkb", &["zve32x"]),
+    ("zvkg", &["zve32x"]),
+    ("zvkn", &["zvkb", "zvkned", "zvknhb", "zvkt"]),
+    ("zvknc", &["zvbc", "zvkn"]),
+    ("zvkned", &["zve32x"]),
+    ("zvkng", &["zvkg", "zvkn"]),
+    ("zvknha", &["zve32x"]),
+    ("zvknhb", &["zve64x", "zvknha"]),
+    ("zvks", &["zvkb", "zvksed", "zvksh", "zvkt"]),
+    ("zvksc", &["zvbc", "zvks"]),
+    ("zvksed", &["zve32x"]),
+    ("zvksg", &["zvkg", "zvks"]),
+    ("zvksh", &["zve32x"]),
+    ("zvl1024b", &["zvl512b"]),
+    ("zvl128b", &["zvl64b"]),
+    ("zvl16384b", &["zvl8192b"]),
+    ("zvl2048b", &["zvl1024b"]),
+    ("zvl256b", &["zvl128b"]),
+    ("zvl32768b", &["zvl16384b"]),
+    ("zvl4096b", &["zvl2048b"]),
+    ("zvl512b", &["zvl256b"]),
+    ("zvl64b", &["zvl32b"]),
+    ("zvl65536b", &["zvl32768b"]),
+    ("zvl8192b", &["zvl4096b"]),
+];
+
+/// Implications of target features: PowerPC (32-bit and 64-bit).
+pub const POWERPC: Implications = &[
+    ("power10-vector", &["power9-vector"]),
+    ("power8", &["power8-altivec", "power8-vector"]),
+    ("power8-altivec", &["altivec"]),
+    ("power8-crypto", &["power8-altivec"]),
+    ("power8-vector", &["power8-altivec", "vsx"]),
+    ("power9", &["power8", "power9-altivec", "power9-vector"]),
+    ("power9-altivec", &["power8-altivec"]),
+    ("power9-vector", &["power8-vector", "power9-altivec"]),
+    ("vsx", &["altivec"]),
+];
+
+/// Implications of target features: LoongArch (32-bit and 64-bit).
+pub const LOONGARCH: Implications = &[
+    ("d", &["f"]),
+    ("frecipe", &["f"]),
+    ("lasx", &["lsx"]),
+    ("lsx", &["d"]),
+];
+
+/// Implications of target features: s390x.
+pub const S390X: Implications = &[
+    (
+        "message-security-assist-extension4",
+        &["message-security-assist-extension3"],
+    ),
+    (
+        "message-security-assist-extension5",
+        &["message-security-assist-extension4"],
+    ),
+    (
+        "message-security-assist-extension8",
+        &["message-security-assist-extension3"],
+    ),
+    (
+        "message-security-assist-extension9",
+        &[
+            "message-security-assist-extension3",
+            "message-security-assist-extension4",
+        ],
+    ),
+    (
+        "miscellaneous-extensions-3",
+        &["miscellaneous-extensions-2"],
+    ),
+    (
+        "miscellaneous-extensions-4",
+        &["miscellaneous-extensions-3"],
+    ),
+    ("nnp-assist", &["vector"]),
+    ("vector-enhancements-1", &["vector"]),
+    ("vector-enhancements-2", &["vector-enhancements-1"]),
+    ("vector-enhancements-3", &["vector-enhancements-2"]),
+    ("vector-packed-decimal", &["vector"]),
+    (
+        "vector-packed-decimal-enhancement",
+        &["vector-packed-decimal"],
+    ),
+    (
+        "vector-packed-decimal-enhancement-2",
+        &["vector-packed-decimal-enhancement"],
+    ),
+    (
+        "vector-packed-decimal-enhancement-3",
+        &["vector-packed-decimal-enhancement-2"],
+    ),
+];
+
+/// Implications of target features: WebAssembly (32-bit and 64-bit).
+pub const WASM: &[(&str, &[&str])] =
+    &[("gc", &["reference-types"]), ("relaxed-simd", &["simd128"])];
+
+/// Maximum depth of the implication chain to follow.
+const MAX_DEPTH: usize = 32;
+
+/// Returns whether `target` is `feature` itself or implied by `feature`.
+const fn implied_by(graphs: &[&[Implications]], feature: &str, target: &str, depth: usize) -> bool {
+    if str_eq(feature, target) {
+        return true;
+    }
+    if depth == 0 {
+        return false;
+    }
+    let mut a = 0;
+    while a < graphs.len() {
+        let mut t = 0;
+        while t < graphs[a].len() {
+            let graph = graphs[a][t];
+            let mut i = 0;
+            while i < graph.len() {
+                if str_eq(graph[i].0, feature) {
+                    let implied = graph[i].1;
+                    let mut j = 0;
+                    while j < implied.len() {
+                        if implied_by(graphs, implied[j], target, depth - 1) {
+                            return true;
+                        }
+                        j += 1;
+                    }
+                }
+                i += 1;
+            }
+            t += 1;
+        }
+        a += 1;
+    }
+    false
+}
+
+/// Returns whether the term (a conjunction) implies `target`.
+const fn term_implies_feature(graphs: &[&[Implications]], term: &[&[&str]], target: &str) -> bool {
+    let mut f = 0;
+    while f < term.len() {
+        let mut i = 0;
+        while i < term[f].len() {
+            if implied_by(graphs, term[f][i], target, MAX_DEPTH) {
+                return true;
+            }
+            i += 1;
+        }
+        f += 1;
+    }
+    false
+}
+
+/// Returns whether the term `lhs` implies the term `rhs`.
+const fn term_implies_term(graphs: &[&[Implications]], lhs: &[&[&str]], rhs: &[&[&str]]) -> bool {
+    let mut f = 0;
+    while f < rhs.len() {
+        let mut i = 0;
+        while i < rhs[f].len() {
+            if !term_implies_feature(graphs, lhs, rhs[f][i]) {
+                return false;
+            }
+            i += 1;
+        }
+        f += 1;
+    }
+    true
+}
+
+/// Returns whether the condition can be analyzed.
+const fn is_known(cond: &[&[&[&str]]]) -> bool {
+    if cond.is_empty() {
+        return false;
+    }
+    let mut t = 0;
+    while t < cond.len() {
+        let mut f = 0;
+        while f < cond[t].len() {
+            if cond[t][f].is_empty() {
+                return false;
+            }
+            f += 1;
+        }
+        t += 1;
+    }
+    true
+}
+
+/// Returns whether the condition `lhs` always implies the condition `rhs`.
+///
+/// `graphs` contains the implication tables per architecture.
+/// This function is conservative: it returns `false` if either of the
+/// conditions cannot be analyzed.
+pub const fn implies(graphs: &[&[Implications]], lhs: &[&[&[&str]]], rhs: &[&[&[&str]]]) -> bool {
+    if !is_known(lhs) || !is_known(rhs) {
+        return false;
+    }
+    // Each term of `lhs` must imply one of the terms of `rhs`.
+    let mut i = 0;
+    while i < lhs.len() {
+        let mut found = false;
+        let mut j = 0;
+        while j < rhs.len() {
+            if term_implies_term(graphs, lhs[i], rhs[j]) {
+                found = true;
+                break;
+            }
+            j += 1;
+        }
+        if !found {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
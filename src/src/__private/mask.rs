@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Runtime masking of features on dynamic dispatching (with the `mask` feature).
+//!
+//! Without the `mask` feature, this module is not compiled and
+//! the macros expand to the bare feature detection.
+
+use core::sync::atomic::Ordering;
+
+use crate::mask::{ACTIVE, DISABLED};
+
+/// Returns whether the feature is not masked on the current thread.
+#[inline]
+pub fn is_enabled(feature: &str) -> bool {
+    ACTIVE.load(Ordering::Relaxed) == 0
+        || DISABLED
+            .try_with(|disabled| !disabled.borrow().iter().any(|f| f == feature))
+            .unwrap_or(true)
+}
+
+/// Returns whether any feature is masked on the current thread.
+#[inline]
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed) != 0
+        && DISABLED
+            .try_with(|disabled| !disabled.borrow().is_empty())
+            .unwrap_or(false)
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Compile-time lookup of proof tokens.
+//!
+//! Each proof token is an instance of [`FeatureToken`](crate::tokens::FeatureToken)
+//! parameterized by the index of the feature in the list of features with
+//! proof tokens on the target architecture.
+
+use super::features::{Message, str_eq};
+use crate::tokens::FEATURES;
+
+/// Returns the index of the feature with a proof token.
+///
+/// # Panics
+///
+/// Panics (causes a compilation error in the constant context) if the feature
+/// has no proof token on the target architecture.
+pub const fn index(feature: &str) -> usize {
+    let mut i = 0;
+    while i < FEATURES.len() {
+        if str_eq(FEATURES[i], feature) {
+            return i;
+        }
+        i += 1;
+    }
+    let msg = Message::new()
+        .push("no proof token for target feature \"")
+        .push(feature)
+        .push("\" on this architecture");
+    panic!("{}", msg.as_str());
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Verification of statically enabled features (with the `verify-static`
+//! feature; only on debug builds of the crate using the macro).
+//!
+//! The feature detection macros in the standard library return `true` for
+//! features enabled on compilation without checking the running CPU.
+//! So, the running CPU is queried directly by CPUID (and XGETBV).
+//! See [`super::cpuid`].
+
+use super::cpuid::is_available;
+
+/// Panics if the feature enabled on compilation is not available on
+/// the running CPU.
+#[track_caller]
+pub fn check(enabled: bool, feature: &'static str) {
+    if enabled && is_available(feature) == Some(false) {
+        panic!(
+            "target feature `{feature}` is enabled on compilation \
+             but not available on the running CPU"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Runtime vector lengths (with the `vlen` feature).
+//!
+//! Functions in this module execute vector instructions and must be called
+//! only after the corresponding vector extension is detected (otherwise,
+//! the program will be killed by an illegal instruction exception).
+//! So, they are `unsafe` and the macros call them only after the feature
+//! check (`"v"` or `"sve"`) short-circuits.
+
+#![allow(unsafe_code)]
+
+/// Returns VLEN (the vector register length in bits) of the RISC-V
+/// "V" extension.
+///
+/// # Safety
+///
+/// The "V" extension must be available on the running CPU
+/// (the `vlenb` CSR does not exist otherwise).
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+pub unsafe fn vlen() -> usize {
+    let vlenb: usize;
+    // SAFETY: reading the `vlenb` CSR (0xC22) has no side effects and
+    // the caller checks that "V" is available.
+    // The CSR number is used so that the assembler does not require "V".
+    unsafe {
+        core::arch::asm!(
+            "csrr {0}, 0xc22",
+            out(reg) vlenb,
+            options(nomem, nostack, preserves_flags)
+        );
+    }
+    vlenb * 8
+}
+
+/// Reads the SVE vector length in bytes.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "sve")]
+unsafe fn rdvl() -> u64 {
+    let vl: u64;
+    // SAFETY: the caller checks that SVE is available.
+    unsafe {
+        core::arch::asm!(
+            "rdvl {0}, #1",
+            out(reg) vl,
+            options(nomem, nostack, preserves_flags)
+        );
+    }
+    vl
+}
+
+/// Returns the SVE vector length in bits.
+///
+/// # Safety
+///
+/// SVE must be available on the running CPU.
+#[cfg(target_arch = "aarch64")]
+pub unsafe fn sve_vl() -> usize {
+    // SAFETY: the caller checks that SVE is available.
+    (unsafe { rdvl() }) as usize * 8
+}
+
+/// Returns the SVE vector length in bits
+/// (always zero because SVE is not available on Arm64EC).
+///
+/// # Safety
+///
+/// This function is always safe to call (`unsafe` only for consistency
+/// with AArch64).
+#[cfg(target_arch = "arm64ec")]
+pub unsafe fn sve_vl() -> usize {
+    0
+}
@@ -0,0 +1,206 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Feature detection on Linux by the auxiliary vector.
+//!
+//! The `auxv` variants of the dispatching modes
+//! (`#[dynamic(auxv)]` and `#[cached(auxv)]`) use this detector
+//! instead of `std::arch::is_*_feature_detected!`, making dynamic
+//! dispatching on Arm (32-bit), PowerPC (32-bit and 64-bit), LoongArch (64-bit)
+//! and s390x available on Stable Rust (detection macros in the standard
+//! library for those architectures require Nightly features).
+//!
+//! ```
+//! use target_feature_dispatch::target_feature_dispatch;
+//!
+//! fn select() -> &'static str {
+//!     target_feature_dispatch! {
+//!         #[dynamic(auxv)]
+//!         if "arm" {
+//!             if "neon" {
+//!                 "NEON"
+//!             } else {
+//!                 "Arm"
+//!             }
+//!         } else {
+//!             "fallback"
+//!         }
+//!     }
+//! }
+//! # let _ = select();
+//! ```
+//!
+//! Features are detected by `AT_HWCAP` and `AT_HWCAP2` entries read from
+//! `/proc/self/auxv` (on the first detection; cached afterwards).
+//! If the file is not readable, no features are detected at runtime
+//! (but still selected if enabled on compilation).
+//! Features unknown to this module are never detected at runtime.
+//!
+//! This module requires the `auxv` feature (which implies the `std` feature)
+//! and is only available on Linux with the architectures above.
+
+use std::sync::OnceLock;
+
+/// Auxiliary vector entry types (`AT_*`).
+mod at {
+    /// End of the vector.
+    pub const NULL: usize = 0;
+    /// Hardware capabilities.
+    pub const HWCAP: usize = 16;
+    /// Hardware capabilities (extended).
+    pub const HWCAP2: usize = 26;
+}
+
+/// Hardware capabilities (`AT_HWCAP` and `AT_HWCAP2`).
+#[derive(Clone, Copy, Debug, Default)]
+struct HwCaps {
+    /// `AT_HWCAP`.
+    hwcap: usize,
+    /// `AT_HWCAP2`.
+    hwcap2: usize,
+}
+
+impl HwCaps {
+    /// Parses the auxiliary vector (a sequence of native word pairs).
+    fn parse(auxv: &[u8]) -> Self {
+        const WORD: usize = size_of::<usize>();
+        let mut caps = Self::default();
+        for entry in auxv.chunks_exact(2 * WORD) {
+            let (key, value) = entry.split_at(WORD);
+            let key = usize::from_ne_bytes(key.try_into().unwrap());
+            let value = usize::from_ne_bytes(value.try_into().unwrap());
+            match key {
+                at::NULL => break,
+                at::HWCAP => caps.hwcap = value,
+                at::HWCAP2 => caps.hwcap2 = value,
+                _ => {}
+            }
+        }
+        caps
+    }
+
+    /// Returns hardware capabilities of the current process.
+    fn get() -> Self {
+        /// Cached hardware capabilities.
+        static CAPS: OnceLock<HwCaps> = OnceLock::new();
+        *CAPS.get_or_init(|| {
+            std::fs::read("/proc/self/auxv")
+                .map(|auxv| Self::parse(&auxv))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Tests a bit of `AT_HWCAP`.
+    #[inline]
+    const fn hwcap(&self, bit: u32) -> bool {
+        self.hwcap & (1 << bit) != 0
+    }
+
+    /// Tests a bit of `AT_HWCAP2`.
+    #[inline]
+    #[allow(dead_code)]
+    const fn hwcap2(&self, bit: u32) -> bool {
+        self.hwcap2 & (1 << bit) != 0
+    }
+
+    /// Returns whether the feature is available
+    /// (or [`None`] if the feature is unknown).
+    ///
+    /// See `arch/arm/include/uapi/asm/hwcap.h` of Linux.
+    #[cfg(target_arch = "arm")]
+    fn lookup(&self, feature: &str) -> Option<bool> {
+        Some(match feature {
+            "vfp2" => self.hwcap(6),
+            "dsp" => self.hwcap(7),
+            "neon" => self.hwcap(12),
+            "vfp3" => self.hwcap(13),
+            "vfp4" => self.hwcap(16),
+            "d32" => self.hwcap(19),
+            "dotprod" => self.hwcap(24),
+            "i8mm" => self.hwcap(27),
+            "aes" => self.hwcap2(0),
+            "pmull" => self.hwcap2(1),
+            // SHA1 and SHA2
+            "sha2" => self.hwcap2(2) && self.hwcap2(3),
+            "crc" => self.hwcap2(4),
+            _ => return None,
+        })
+    }
+
+    /// Returns whether the feature is available
+    /// (or [`None`] if the feature is unknown).
+    ///
+    /// See `arch/powerpc/include/uapi/asm/cputable.h` of Linux.
+    #[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+    fn lookup(&self, feature: &str) -> Option<bool> {
+        let altivec = self.hwcap(28);
+        let vsx = self.hwcap(7);
+        let isa_2_07 = self.hwcap2(31);
+        let isa_3_00 = self.hwcap2(23);
+        let isa_3_1 = self.hwcap2(18);
+        Some(match feature {
+            "altivec" => altivec,
+            "vsx" => vsx,
+            "power8" => isa_2_07,
+            "power9" => isa_3_00,
+            "power8-altivec" => altivec && isa_2_07,
+            "power8-vector" => vsx && isa_2_07,
+            "power8-crypto" => altivec && self.hwcap2(25),
+            "power9-altivec" => altivec && isa_3_00,
+            "power9-vector" => vsx && isa_3_00,
+            "power10-vector" => vsx && isa_3_1,
+            _ => return None,
+        })
+    }
+
+    /// Returns whether the feature is available
+    /// (or [`None`] if the feature is unknown).
+    ///
+    /// See `arch/loongarch/include/uapi/asm/hwcap.h` of Linux.
+    #[cfg(target_arch = "loongarch64")]
+    fn lookup(&self, feature: &str) -> Option<bool> {
+        Some(match feature {
+            "ual" => self.hwcap(2),
+            // FPU (both single and double precision)
+            "f" | "d" => self.hwcap(3),
+            "lsx" => self.hwcap(4),
+            "lasx" => self.hwcap(5),
+            "lvz" => self.hwcap(9),
+            // x86, Arm and MIPS binary translation
+            "lbt" => self.hwcap(10) && self.hwcap(11) && self.hwcap(12),
+            _ => return None,
+        })
+    }
+
+    /// Returns whether the feature is available
+    /// (or [`None`] if the feature is unknown).
+    ///
+    /// See `arch/s390/include/asm/elf.h` of Linux.
+    #[cfg(target_arch = "s390x")]
+    fn lookup(&self, feature: &str) -> Option<bool> {
+        Some(match feature {
+            "high-word" => self.hwcap(9),
+            "transactional-execution" => self.hwcap(10),
+            "vector" => self.hwcap(11),
+            "vector-packed-decimal" => self.hwcap(12),
+            "vector-enhancements-1" => self.hwcap(13),
+            "guarded-storage" => self.hwcap(14),
+            "vector-enhancements-2" => self.hwcap(15),
+            "vector-packed-decimal-enhancement" => self.hwcap(16),
+            "enhanced-sort" => self.hwcap(17),
+            "deflate-conversion" => self.hwcap(18),
+            "vector-packed-decimal-enhancement-2" => self.hwcap(19),
+            "nnp-assist" => self.hwcap(20),
+            _ => return None,
+        })
+    }
+}
+
+/// Returns whether the feature is available on the running CPU
+/// (as reported by the kernel).
+///
+/// Unlike feature detection macros in the standard library,
+/// features enabled on compilation are not assumed to be available.
+pub fn is_detected(feature: &str) -> bool {
+    HwCaps::get().lookup(feature) == Some(true)
+}
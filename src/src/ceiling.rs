@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Ceiling of detected features by the environment variable.
+//!
+//! Features listed in the environment variable named by [`DISABLE_VAR`]
+//! (`TARGET_FEATURE_DISPATCH_DISABLE`; comma-separated) are treated as
+//! absent when the per-site cache of cached dynamic dispatching
+//! (`#[cached]` and `dispatch_static!`) is filled.  This is useful to stop
+//! a deployed binary from taking a specific path without rebuilding.
+//!
+//! ```sh
+//! TARGET_FEATURE_DISPATCH_DISABLE=avx512f,avx2 ./program
+//! ```
+//!
+//! Note that:
+//!
+//! *   The variable is read once (on the first fill of a cache) and
+//!     changes after that are ignored.
+//! *   The ceiling is applied only once per site (when the cache is filled)
+//!     and the cached result is used as usual after that.
+//!     Uncached dynamic dispatching (`#[dynamic]`) is not affected.
+//! *   It can only lower (never raise) the set of detected features.
+//! *   Implications of features are not followed.
+//!     For instance, disabling `"avx"` does not disable `"avx2"`.
+//!
+//! This module requires the `env-ceiling` feature (which enables `std`).
+//! Without it, the variable is never read.
+
+use std::string::String;
+use std::sync::OnceLock;
+use std::vec::Vec;
+
+/// The name of the environment variable listing features to disable.
+pub const DISABLE_VAR: &str = "TARGET_FEATURE_DISPATCH_DISABLE";
+
+/// Features disabled by the environment variable.
+static DISABLED: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Returns features disabled by the environment variable
+/// (read on the first call).
+pub(crate) fn disabled() -> &'static [String] {
+    DISABLED.get_or_init(|| match std::env::var(DISABLE_VAR) {
+        Ok(value) => value
+            .split(',')
+            .map(str::trim)
+            .filter(|feature| !feature.is_empty())
+            .map(String::from)
+            .collect(),
+        Err(_) => Vec::new(),
+    })
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Feature detection on x86 without the standard library.
+//!
+//! The `core` variants of the dispatching modes
+//! (`#[dynamic(core)]` and `#[cached(core)]`) use this detector
+//! instead of `std::arch::is_x86_feature_detected!`, making dynamic
+//! dispatching on x86 available to `no_std` crates (e.g. kernels and
+//! bootloaders).
+//!
+//! ```
+//! use target_feature_dispatch::target_feature_dispatch;
+//!
+//! fn select() -> &'static str {
+//!     target_feature_dispatch! {
+//!         #[dynamic(core)]
+//!         if family("x86") {
+//!             if "avx2" {
+//!                 "AVX2"
+//!             } else {
+//!                 "x86"
+//!             }
+//!         } else {
+//!             "fallback"
+//!         }
+//!     }
+//! }
+//! # let _ = select();
+//! ```
+//!
+//! Features are detected by CPUID and availability of the AVX and AVX-512
+//! states is checked by XGETBV (XCR0).  Results of CPUID are cached in atomic
+//! variables.  Features unknown to this module (including AMX features) are
+//! never detected by [`is_detected`] and cause a compilation error on
+//! `#[dynamic(core)]` and `#[cached(core)]`.
+//!
+//! This module requires the `cpuid` feature (which allows unsafe code in
+//! this crate) and is only available on x86 (32-bit and 64-bit).
+
+/// Returns whether the feature is available on the running CPU.
+///
+/// Unlike [`is_x86_feature_detected!`](std::arch::is_x86_feature_detected),
+/// features enabled on compilation are not assumed to be available.
+pub fn is_detected(feature: &str) -> bool {
+    crate::__private::cpuid::is_available(feature) == Some(true)
+}
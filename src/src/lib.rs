@@ -4,9 +4,22 @@
 #![doc = include_str!("../docs/readme.md")]
 // no_std by default.
 #![no_std]
-// Unsafe code is not allowed.  Forbid it unless in the tests and documentation.
+// Unsafe code is not allowed except in the modules which explicitly allow it
+// (linker-defined symbols for the `sites` feature, CPUID for the `cpuid` and `verify-static` features
+// and vector length instructions for the `vlen` feature).
+// Forbid it unless in the tests, documentation or with one of those features.
 #![deny(unsafe_code)]
-#![cfg_attr(not(any(test, doc)), forbid(unsafe_code))]
+#![cfg_attr(
+    not(any(
+        test,
+        doc,
+        feature = "sites",
+        feature = "cpuid",
+        feature = "verify-static",
+        feature = "vlen"
+    )),
+    forbid(unsafe_code)
+)]
 // In the code maintenance mode, disallow all warnings.
 #![cfg_attr(feature = "maint-code", deny(warnings))]
 // Non-test code requires documents.
@@ -17,10 +30,39 @@
     allow(unknown_lints, renamed_and_removed_lints)
 )]
 
-// Import std on documentation.
-#[cfg(doc)]
+// Import std on documentation or with the `std` feature.
+#[cfg(any(doc, feature = "std"))]
 extern crate std;
 
+// Support code for the macros (not a part of the public API).
+#[doc(hidden)]
+pub mod __private;
+
+#[cfg(all(
+    feature = "auxv",
+    target_os = "linux",
+    any(
+        target_arch = "arm",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "loongarch64",
+        target_arch = "s390x"
+    )
+))]
+pub mod auxv;
+#[cfg(feature = "env-ceiling")]
+pub mod ceiling;
+#[cfg(all(feature = "cpuid", any(target_arch = "x86", target_arch = "x86_64")))]
+pub mod cpuid;
+#[cfg(feature = "mask")]
+pub mod mask;
+#[cfg(feature = "sites")]
+pub mod sites;
+#[cfg(feature = "sites")]
+pub use sites::sites;
+#[cfg(feature = "proof-tokens")]
+pub mod tokens;
+
 #[doc = include_str!("../docs/target_feature_dispatch.md")]
 #[macro_export]
 macro_rules! target_feature_dispatch {
@@ -30,13 +72,37 @@ macro_rules! target_feature_dispatch {
         Dynamic dispatching: default-disabled (any())
         Nightly features:    default-disabled (any())
         Non-fallback paths:  default-enabled  (all())
+        Cached dispatching:  default-disabled (any())
+        Enabling features:   default-disabled (any())
+        All branches:        default-disabled (any())
+        Selected label:      default-disabled (any())
+        Verifying features:  default-disabled (any())
+    */
+    (
+        $(#[$($pseudo_meta: tt)+])*
+        if $($arch1: tt $(($arch1_arg: tt))?)||+ { $($if1: tt)* }
+        $(else $(#[cfg($cfg: meta)])* if $($arch: tt $(($arch_arg: tt))?)||+ { $($if: tt)* })*
+        else { $($else: tt)* }
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options
+            (any(), any(), all(), any(), any(), any(), any(), any(), [])
+            $(#[$($pseudo_meta)+])*
+            ($($else)*) (($($arch1$(($arch1_arg))?)||+) ($($if1)*))
+            $($(#[cfg($cfg)])* (($($arch$(($arch_arg))?)||+) ($($if)*)))*
+        )
+    };
+
+    /*
+        Parse options only (used by other macros in this crate).
+        The callback macro is invoked with parsed options appended.
     */
-    ($(#[$($pseudo_meta: tt)+])* $(if $($arch: tt $(($arch_arg: tt))?)||+ { $($if: tt)* })else+ else { $($else: tt)* }) => {
+    (@__tgtfeat_dispatch_options_then [$($callback: tt)+] ($($args: tt)*) $(#[$($pseudo_meta: tt)+])*) => {
         $crate::target_feature_dispatch!(
             @__tgtfeat_dispatch_parse_options
-            (any(), any(), all())
+            (any(), any(), all(), any(), any(), any(), any(), any(), [])
             $(#[$($pseudo_meta)+])*
-            ($($else)*) $((($($arch$(($arch_arg))?)||+) ($($if)*)))+
+            { @__tgtfeat_dispatch_callback [$($callback)+] ($($args)*) }
         )
     };
 
@@ -44,506 +110,2420 @@ macro_rules! target_feature_dispatch {
         Parse options.
     */
     // Unconditional "dynamic".
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) #[dynamic] $($rest: tt)+) => {
-        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options (all(), $nightly, $dispatch) $($rest)+)
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[dynamic] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options (all(), $nightly, $dispatch, any(), $enable, $all, $query, $verify, [$($det)*]) $($rest)+)
     };
     // Unconditional "static".
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) #[static] $($rest: tt)+) => {
-        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options (any(), $nightly, $dispatch) $($rest)+)
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[static] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options (any(), $nightly, $dispatch, any(), $enable, $all, $query, $verify, [$($det)*]) $($rest)+)
+    };
+    // Unconditional "cached".
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[cached] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options (all(), $nightly, $dispatch, all(), $enable, $all, $query, $verify, [$($det)*]) $($rest)+)
     };
     // Unconditional "unstable".
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) #[unstable] $($rest: tt)+) => {
-        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options ($dyn, all(), $dispatch) $($rest)+)
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[unstable] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options ($dyn, all(), $dispatch, $cached, $enable, $all, $query, $verify, [$($det)*]) $($rest)+)
     };
     // Unconditional "stable".
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) #[stable] $($rest: tt)+) => {
-        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options ($dyn, any(), $dispatch) $($rest)+)
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[stable] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options ($dyn, any(), $dispatch, $cached, $enable, $all, $query, $verify, [$($det)*]) $($rest)+)
+    };
+    // Unconditional "enable_features".
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[enable_features] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options ($dyn, $nightly, $dispatch, $cached, all(), $all, $query, $verify, [$($det)*]) $($rest)+)
+    };
+    // Unconditional "all_branches" (implies "dynamic").
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[all_branches] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options (all(), $nightly, $dispatch, any(), $enable, all(), $query, $verify, [$($det)*]) $($rest)+)
+    };
+    // Unconditional "selected_label".
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[selected_label] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options ($dyn, $nightly, $dispatch, $cached, $enable, $all, all(), $verify, [$($det)*]) $($rest)+)
+    };
+    // Unconditional "verify_static".
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[verify_static] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_parse_options ($dyn, $nightly, $dispatch, $cached, $enable, $all, $query, all(), [$($det)*]) $($rest)+)
+    };
+    // Registration of the dispatching site (only on expressions).
+    (
+        @__tgtfeat_dispatch_parse_options ($($opts: tt)*)
+        #[site = $name: literal] $(#[$($pmeta: tt)+])*
+        { @__tgtfeat_dispatch_callback $($rest: tt)* }
+    ) => {
+        compile_error!("`site` is only supported in `target_feature_dispatch!`");
+    };
+    // Registration of the dispatching site (see the `sites` module).
+    // The resolver and the labels are evaluated by this macro with the
+    // same options (and `selected_label`).
+    (
+        @__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*])
+        #[site = $name: literal] $($rest: tt)+
+    ) => {
+        {
+            $crate::__tgtfeat_dispatch_register_site! {
+                $name,
+                || $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_parse_options
+                    ($dyn, $nightly, $dispatch, $cached, $enable, any(), all(), any(), [$($det)*]) $($rest)+
+                ),
+                |f| {
+                    for label in $crate::target_feature_dispatch!(
+                        @__tgtfeat_dispatch_parse_options
+                        ($dyn, $nightly, $dispatch, $cached, $enable, all(), all(), any(), [$($det)*]) $($rest)+
+                    ) {
+                        f(label);
+                    }
+                }
+            }
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_parse_options
+                ($dyn, $nightly, $dispatch, $cached, $enable, $all, $query, $verify, [$($det)*]) $($rest)+
+            )
+        }
     };
     // Conditional "dynamic".
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) #[cfg_attr($meta: meta, dynamic)] $($rest: tt)+) => {
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[cfg_attr($meta: meta, dynamic)] $($rest: tt)+) => {
         $crate::target_feature_dispatch!(
             @__tgtfeat_dispatch_parse_options
-            (any($dyn, $meta), $nightly, $dispatch)
+            (any($dyn, $meta), $nightly, $dispatch, all($cached, not($meta)), $enable, $all, $query, $verify, [$($det)*])
             $($rest)+
         )
     };
     // Conditional "static".
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) #[cfg_attr($meta: meta, static)] $($rest: tt)+) => {
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[cfg_attr($meta: meta, static)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options
+            (all($dyn, not($meta)), $nightly, $dispatch, all($cached, not($meta)), $enable, $all, $query, $verify, [$($det)*])
+            $($rest)+
+        )
+    };
+    // Conditional "cached".
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[cfg_attr($meta: meta, cached)] $($rest: tt)+) => {
         $crate::target_feature_dispatch!(
             @__tgtfeat_dispatch_parse_options
-            (all($dyn, not($meta)), $nightly, $dispatch)
+            (any($dyn, $meta), $nightly, $dispatch, any($cached, $meta), $enable, $all, $query, $verify, [$($det)*])
             $($rest)+
         )
     };
     // Conditional "unstable".
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) #[cfg_attr($meta: meta, unstable)] $($rest: tt)+) => {
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[cfg_attr($meta: meta, unstable)] $($rest: tt)+) => {
         $crate::target_feature_dispatch!(
             @__tgtfeat_dispatch_parse_options
-            ($dyn, any($nightly, $meta), $dispatch)
+            ($dyn, any($nightly, $meta), $dispatch, $cached, $enable, $all, $query, $verify, [$($det)*])
             $($rest)+
         )
     };
     // Conditional "stable".
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) #[cfg_attr($meta: meta, stable)] $($rest: tt)+) => {
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[cfg_attr($meta: meta, stable)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options
+            ($dyn, all($nightly, not($meta)), $dispatch, $cached, $enable, $all, $query, $verify, [$($det)*])
+            $($rest)+
+        )
+    };
+    // Conditional "enable_features".
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[cfg_attr($meta: meta, enable_features)] $($rest: tt)+) => {
         $crate::target_feature_dispatch!(
             @__tgtfeat_dispatch_parse_options
-            ($dyn, all($nightly, not($meta)), $dispatch)
+            ($dyn, $nightly, $dispatch, $cached, any($enable, $meta), $all, $query, $verify, [$($det)*])
+            $($rest)+
+        )
+    };
+    // Conditional "verify_static".
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[cfg_attr($meta: meta, verify_static)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options
+            ($dyn, $nightly, $dispatch, $cached, $enable, $all, $query, any($verify, $meta), [$($det)*])
             $($rest)+
         )
     };
     // Conditional non-fallback paths.
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) #[cfg_non_fallback($meta: meta)] $($rest: tt)+) => {
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[cfg_non_fallback($meta: meta)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options
+            ($dyn, $nightly, all($dispatch, $meta), $cached, $enable, $all, $query, $verify, [$($det)*])
+            $($rest)+
+        )
+    };
+    // "dynamic" and "cached" with the `no_std` detector (x86 only).
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[dynamic(core)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options
+            (
+                any($dyn, target_arch = "x86", target_arch = "x86_64"), $nightly, $dispatch,
+                all($cached, not(any(target_arch = "x86", target_arch = "x86_64"))), $enable, $all, $query, $verify,
+                [((any(target_arch = "x86", target_arch = "x86_64")) ($crate::__tgtfeat_dispatch_detect_cpuid)) $($det)*]
+            )
+            $($rest)+
+        )
+    };
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[cached(core)] $($rest: tt)+) => {
         $crate::target_feature_dispatch!(
             @__tgtfeat_dispatch_parse_options
-            ($dyn, $nightly, all($dispatch, $meta))
+            (
+                any($dyn, target_arch = "x86", target_arch = "x86_64"), $nightly, $dispatch,
+                any($cached, target_arch = "x86", target_arch = "x86_64"), $enable, $all, $query, $verify,
+                [((any(target_arch = "x86", target_arch = "x86_64")) ($crate::__tgtfeat_dispatch_detect_cpuid)) $($det)*]
+            )
+            $($rest)+
+        )
+    };
+    // "dynamic" and "cached" with the auxiliary vector detector
+    // (Arm, PowerPC, LoongArch and s390x on Linux).
+    (@__tgtfeat_dispatch_parse_options ($($opts: tt)*) #[dynamic(auxv)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options ($($opts)*)
+            #[cfg_attr(target_os = "linux", dynamic("arm" || "powerpc" || "powerpc64" || "loongarch64" || "s390x"))]
+            #[cfg_attr(
+                target_os = "linux",
+                detector("arm" || "powerpc" || "powerpc64" || "loongarch64" || "s390x" => @__tgtfeat_dispatch_trusted $crate::__tgtfeat_dispatch_detect_auxv)
+            )]
+            $($rest)+
+        )
+    };
+    (@__tgtfeat_dispatch_parse_options ($($opts: tt)*) #[cached(auxv)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options ($($opts)*)
+            #[cfg_attr(target_os = "linux", cached("arm" || "powerpc" || "powerpc64" || "loongarch64" || "s390x"))]
+            #[cfg_attr(
+                target_os = "linux",
+                detector("arm" || "powerpc" || "powerpc64" || "loongarch64" || "s390x" => @__tgtfeat_dispatch_trusted $crate::__tgtfeat_dispatch_detect_auxv)
+            )]
+            $($rest)+
+        )
+    };
+    // Architecture-specific "detector" (converted to the conditional form below).
+    (
+        @__tgtfeat_dispatch_parse_options ($($opts: tt)*)
+        #[detector($($arch: tt $(($arch_arg: tt))?)||+ => $($detect: tt)+)] $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_parse_options_arch ($($opts)*) (all()) detector ($($detect)+) ($($rest)+))
+            () ($($arch$(($arch_arg))?)||+)
+        )
+    };
+    (
+        @__tgtfeat_dispatch_parse_options ($($opts: tt)*)
+        #[cfg_attr($meta: meta, detector($($arch: tt $(($arch_arg: tt))?)||+ => $($detect: tt)+))] $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_parse_options_arch ($($opts)*) ($meta) detector ($($detect)+) ($($rest)+))
+            () ($($arch$(($arch_arg))?)||+)
+        )
+    };
+    // User-defined "detector" (a macro or a function).
+    (@__tgtfeat_dispatch_parse_options ($($opts: tt)*) #[detector($($detect: tt)+)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options ($($opts)*)
+            #[cfg_attr(all(), detector($($detect)+))] $($rest)+
+        )
+    };
+    (@__tgtfeat_dispatch_parse_options ($($opts: tt)*) #[cfg_attr($meta: meta, detector($($detect: tt)+))] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_detector ($($opts)*) ($meta) [$($detect)+] ($($detect)+)
             $($rest)+
         )
     };
+    // Architecture-specific "dynamic", "static" and "cached"
+    // (converted to the conditional forms above).
+    (@__tgtfeat_dispatch_parse_options ($($opts: tt)*) #[dynamic($($arch: tt $(($arch_arg: tt))?)||+)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_parse_options_arch ($($opts)*) (all()) dynamic ($($rest)+))
+            () ($($arch$(($arch_arg))?)||+)
+        )
+    };
+    (@__tgtfeat_dispatch_parse_options ($($opts: tt)*) #[static($($arch: tt $(($arch_arg: tt))?)||+)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_parse_options_arch ($($opts)*) (all()) static ($($rest)+))
+            () ($($arch$(($arch_arg))?)||+)
+        )
+    };
+    (@__tgtfeat_dispatch_parse_options ($($opts: tt)*) #[cached($($arch: tt $(($arch_arg: tt))?)||+)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_parse_options_arch ($($opts)*) (all()) cached ($($rest)+))
+            () ($($arch$(($arch_arg))?)||+)
+        )
+    };
+    (
+        @__tgtfeat_dispatch_parse_options ($($opts: tt)*)
+        #[cfg_attr($meta: meta, dynamic($($arch: tt $(($arch_arg: tt))?)||+))] $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_parse_options_arch ($($opts)*) ($meta) dynamic ($($rest)+))
+            () ($($arch$(($arch_arg))?)||+)
+        )
+    };
+    (
+        @__tgtfeat_dispatch_parse_options ($($opts: tt)*)
+        #[cfg_attr($meta: meta, static($($arch: tt $(($arch_arg: tt))?)||+))] $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_parse_options_arch ($($opts)*) ($meta) static ($($rest)+))
+            () ($($arch$(($arch_arg))?)||+)
+        )
+    };
+    (
+        @__tgtfeat_dispatch_parse_options ($($opts: tt)*)
+        #[cfg_attr($meta: meta, cached($($arch: tt $(($arch_arg: tt))?)||+))] $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_parse_options_arch ($($opts)*) ($meta) cached ($($rest)+))
+            () ($($arch$(($arch_arg))?)||+)
+        )
+    };
+    // `cfg` of the first architecture clause (moved after other pseudo-attributes).
+    (@__tgtfeat_dispatch_parse_options ($($opts: tt)*) #[cfg($meta1: meta)] #[cfg($meta2: meta)] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options ($($opts)*)
+            #[cfg(all($meta1, $meta2))] $($rest)+
+        )
+    };
+    (@__tgtfeat_dispatch_parse_options ($($opts: tt)*) #[cfg($meta: meta)] #[$($pmeta: tt)+] $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options ($($opts)*)
+            #[$($pmeta)+] #[cfg($meta)] $($rest)+
+        )
+    };
+    (@__tgtfeat_dispatch_parse_options ($($opts: tt)*) #[cfg($meta: meta)] ($($else: tt)*) $first: tt $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options ($($opts)*)
+            ($($else)*) #[cfg($meta)] $first $($rest)*
+        )
+    };
     // Invalid pseudo-attribute.
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) #[$($pmeta: tt)+] $($rest: tt)+) => {
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) #[$($pmeta: tt)+] $($rest: tt)+) => {
         compile_error!(concat!("invalid pseudo-attribute: ", stringify!(#[$($pmeta)+])));
     };
+    // No more options (pass to the callback).
+    (
+        @__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*])
+        { @__tgtfeat_dispatch_callback [$($callback: tt)+] ($($args: tt)*) }
+    ) => {
+        $($callback)+! { $($args)* ($dyn, $nightly, $dispatch, $cached, $enable, $all, $query, $verify, [$($det)*]) }
+    };
     // No more options (pass to the architecture-specific chain).
-    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta) ($($else: tt)*) $(($($ifs: tt)+))+) => {
+    (@__tgtfeat_dispatch_parse_options ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) ($($else: tt)*) $($ifs: tt)+) => {
         {
             #[cfg($dispatch)]
             {
                 $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($dyn, $nightly)
-                    ($($else)*) $(($($ifs)+))+
+                    @__tgtfeat_dispatch_arch_chain ($dyn, $nightly, $cached, $enable, $all, $query, $verify, [$($det)*])
+                    ($($else)*) $($ifs)+
                 )
             }
             #[cfg(not($dispatch))]
             {
-                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($else)*)
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_single ($all, $query) $($else)*)
             }
         }
     };
 
+    // Architecture-specific modes (after conversion to the list of architectures).
+    (
+        @__tgtfeat_dispatch_parse_options_arch ($($opts: tt)*) ($meta: meta) detector ($($detect: tt)+) ($($rest: tt)+)
+        ($($added: tt,)+)
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options ($($opts)*)
+            #[cfg_attr(all($meta, any($(target_arch = $added),+)), detector($($detect)+))] $($rest)+
+        )
+    };
+    (
+        @__tgtfeat_dispatch_parse_options_arch ($($opts: tt)*) ($meta: meta) $mode: tt ($($rest: tt)+)
+        ($($added: tt,)+)
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options ($($opts)*)
+            #[cfg_attr(all($meta, any($(target_arch = $added),+)), $mode)] $($rest)+
+        )
+    };
+
     /*
-        Architecture-specific `if`-`else` chain.
-        Note that families are also parsed in @__tgtfeat_dispatch_arch_chain_2.
+        User-defined detector.
+
+        Since a detector decides which clauses (possibly with unsafe code
+        generated by this macro) are selected, it must be marked `unsafe`.
+        The `unsafe` token written by the user is kept (as the first copy)
+        so that the detection result is trusted in the user's `unsafe` block.
+        Built-in detectors are marked by `@__tgtfeat_dispatch_trusted`.
     */
-    // `if`: family("aarch64") → any(target_arch = "aarch64", target_arch = "arm64ec")
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) ((family("aarch64")) ($($if: tt)*)) $($rest: tt)*) => {
-        {
-            #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec"))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause (family("aarch64")) ($($opts),*)
-                    ($($else)*) ($($if)*)
-                )
-            }
-            #[cfg(not(any(target_arch = "aarch64", target_arch = "arm64ec")))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
-                    ($($else)*) $($rest)*
-                )
-            }
+    (
+        @__tgtfeat_dispatch_parse_detector
+        ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*])
+        ($meta: meta) [$unsafe: tt $($detect: tt)+] (unsafe $($_detect: tt)+) $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options
+            ($dyn, $nightly, $dispatch, $cached, $enable, $all, $query, $verify, [(($meta) (@ $unsafe $($detect)+)) $($det)*])
+            $($rest)+
+        )
+    };
+    (
+        @__tgtfeat_dispatch_parse_detector
+        ($dyn: meta, $nightly: meta, $dispatch: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*])
+        ($meta: meta) [$($_detect: tt)+] (@__tgtfeat_dispatch_trusted $($detect: tt)+) $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_parse_options
+            ($dyn, $nightly, $dispatch, $cached, $enable, $all, $query, $verify, [(($meta) ($($detect)+)) $($det)*])
+            $($rest)+
+        )
+    };
+    (@__tgtfeat_dispatch_parse_detector ($($opts: tt)*) ($meta: meta) [$($detect: tt)+] ($($_detect: tt)+) $($rest: tt)+) => {
+        compile_error!(concat!(
+            "user-defined detector must be marked `unsafe` ",
+            "(e.g. `detector(unsafe ", stringify!($($detect)+), ")`; see the documentation for the safety contract)"
+        ));
+    };
+
+    /*
+        List of architectures.
+        Family / class specifiers are expanded and the callback macro is
+        invoked with the list of architectures appended:
+        ("ARCH1", "ARCH2", ...,)
+    */
+    // family("aarch64") → "aarch64" || "arm64ec"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (family("aarch64") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "aarch64", "arm64ec",) ($($($arch2$(($arch2_arg))?)||+)?)
         }
     };
-    // `if`: family("riscv") → any(target_arch = "riscv32", target_arch = "riscv64")
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) ((family("riscv")) ($($if: tt)*)) $($rest: tt)*) => {
-        {
-            #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause (family("riscv")) ($($opts),*)
-                    ($($else)*) ($($if)*)
-                )
-            }
-            #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
-                    ($($else)*) $($rest)*
-                )
-            }
+    // family("riscv") → "riscv32" || "riscv64"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (family("riscv") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "riscv32", "riscv64",) ($($($arch2$(($arch2_arg))?)||+)?)
         }
     };
-    // `if`: family("x86") → any(target_arch = "x86", target_arch = "x86_64")
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) ((family("x86")) ($($if: tt)*)) $($rest: tt)*) => {
-        {
-            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause (family("x86")) ($($opts),*)
-                    ($($else)*) ($($if)*)
-                )
-            }
-            #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
-                    ($($else)*) $($rest)*
-                )
-            }
+    // family("x86") → "x86" || "x86_64"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (family("x86") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "x86", "x86_64",) ($($($arch2$(($arch2_arg))?)||+)?)
         }
     };
-    // `if`: class("arm") → any(target_arch = "aarch64", target_arch = "arm64ec", target_arch = "arm")
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) ((class("arm")) ($($if: tt)*)) $($rest: tt)*) => {
-        {
-            #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec", target_arch = "arm"))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause (class("arm")) ($($opts),*)
-                    ($($else)*) ($($if)*)
-                )
-            }
-            #[cfg(not(any(target_arch = "aarch64", target_arch = "arm64ec", target_arch = "arm")))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
-                    ($($else)*) $($rest)*
-                )
-            }
+    // family("arm") → "aarch64" || "arm64ec" || "arm"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (family("arm") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "aarch64", "arm64ec", "arm",) ($($($arch2$(($arch2_arg))?)||+)?)
         }
     };
-    // `if`: class("mips") → any(target_arch = "mips", target_arch = "mips64", target_arch = "mips32r6", target_arch = "mips64r6")
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) ((class("mips")) ($($if: tt)*)) $($rest: tt)*) => {
-        {
-            #[cfg(any(target_arch = "mips", target_arch = "mips64", target_arch = "mips32r6", target_arch = "mips64r6"))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause (class("mips")) ($($opts),*)
-                    ($($else)*) ($($if)*)
-                )
-            }
-            #[cfg(not(any(target_arch = "mips", target_arch = "mips64", target_arch = "mips32r6", target_arch = "mips64r6")))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
-                    ($($else)*) $($rest)*
-                )
-            }
+    // family("mips") → "mips" || "mips64"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (family("mips") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "mips", "mips64",) ($($($arch2$(($arch2_arg))?)||+)?)
         }
     };
-    // `if`: class("mips-classic") → any(target_arch = "mips", target_arch = "mips64")
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) ((class("mips-classic")) ($($if: tt)*)) $($rest: tt)*) => {
-        {
-            #[cfg(any(target_arch = "mips", target_arch = "mips64"))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause (class("mips-classic")) ($($opts),*)
-                    ($($else)*) ($($if)*)
-                )
-            }
-            #[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
-                    ($($else)*) $($rest)*
-                )
-            }
+    // family("powerpc") → "powerpc" || "powerpc64"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (family("powerpc") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "powerpc", "powerpc64",) ($($($arch2$(($arch2_arg))?)||+)?)
         }
     };
-    // `if`: class("mipsr6") → any(target_arch = "mips32r6", target_arch = "mips64r6")
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) ((class("mipsr6")) ($($if: tt)*)) $($rest: tt)*) => {
-        {
-            #[cfg(any(target_arch = "mips32r6", target_arch = "mips64r6"))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause (class("mipsr6")) ($($opts),*)
-                    ($($else)*) ($($if)*)
-                )
+    // class("arm") → "aarch64" || "arm64ec" || "arm"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (class("arm") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "aarch64", "arm64ec", "arm",) ($($($arch2$(($arch2_arg))?)||+)?)
+        }
+    };
+    // class("mips") → "mips" || "mips64" || "mips32r6" || "mips64r6"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (class("mips") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "mips", "mips64", "mips32r6", "mips64r6",) ($($($arch2$(($arch2_arg))?)||+)?)
+        }
+    };
+    // class("mips-classic") → "mips" || "mips64"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (class("mips-classic") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "mips", "mips64",) ($($($arch2$(($arch2_arg))?)||+)?)
+        }
+    };
+    // class("mipsr6") → "mips32r6" || "mips64r6"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (class("mipsr6") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "mips32r6", "mips64r6",) ($($($arch2$(($arch2_arg))?)||+)?)
+        }
+    };
+    // class("powerpc") → "powerpc" || "powerpc64"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (class("powerpc") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "powerpc", "powerpc64",) ($($($arch2$(($arch2_arg))?)||+)?)
+        }
+    };
+    // class("sparc") → "sparc" || "sparc64"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (class("sparc") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "sparc", "sparc64",) ($($($arch2$(($arch2_arg))?)||+)?)
+        }
+    };
+    // class("wasm") → "wasm32" || "wasm64"
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) (class("wasm") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* "wasm32", "wasm64",) ($($($arch2$(($arch2_arg))?)||+)?)
+        }
+    };
+    // User-defined family / class (query the architectures).
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) ($kind: ident($name: ident) $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $name! {
+            @__tgtfeat_dispatch_arch_set $kind [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_arch_list_user [$($callback)+] ($($args)*) ($($added,)*) ($($($arch2$(($arch2_arg))?)||+)?))
+        }
+    };
+    (
+        @__tgtfeat_dispatch_arch_list_user [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) ($($rest: tt)*) [$($member: tt),+]
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* $($member,)+) ($($rest)*)
+        }
+    };
+    // Others
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) ($arch1: tt ($arch1_arg: tt) $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        compile_error!(concat!("Invalid architecture specifier: ", stringify!($arch1($arch1_arg))));
+    };
+    (
+        @__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*)
+        ($($added: tt,)*) ($arch1: tt $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?)
+    ) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$($callback)+] ($($args)*)
+            ($($added,)* $arch1,) ($($($arch2$(($arch2_arg))?)||+)?)
+        }
+    };
+    // Conversion is completed.
+    (@__tgtfeat_dispatch_arch_list [$($callback: tt)+] ($($args: tt)*) ($($added: tt,)+) ()) => {
+        $($callback)+! { $($args)* ($($added,)+) }
+    };
+
+    /*
+        Definition of a user-defined family / class
+        (used by `define_arch_family` and `define_arch_class`).
+        The defined macro returns the list of architectures:
+        [ "ARCH1", "ARCH2", ... ]
+    */
+    (
+        @__tgtfeat_dispatch_define_arch_set ($d: tt) $kind: ident ($(#[$attr: meta])*) (pub) $name: ident
+        ($($member: tt,)+)
+    ) => {
+        $(#[$attr])*
+        #[macro_export]
+        macro_rules! $name {
+            (@__tgtfeat_dispatch_arch_set $kind [$d($d callback: tt)+] ($d($d args: tt)*)) => {
+                $d($d callback)+! { $d($d args)* [$($member),+] }
+            };
+            (@__tgtfeat_dispatch_arch_set $d other: ident $d($d rest: tt)*) => {
+                compile_error!(concat!(
+                    "`", stringify!($name), "` is not a ", stringify!($d other),
+                    " (defined as a ", stringify!($kind), ")"
+                ));
+            };
+            (@__tgtfeat_dispatch_feature_set $d($d rest: tt)*) => {
+                compile_error! {
+                    concat!("`", stringify!($name), "` is not a feature set (defined as a ", stringify!($kind), ")")
+                }
+            };
+        }
+    };
+    (
+        @__tgtfeat_dispatch_define_arch_set ($d: tt) $kind: ident ($(#[$attr: meta])*) ($vis: vis) $name: ident
+        ($($member: tt,)+)
+    ) => {
+        $(#[$attr])*
+        #[allow(unused_macros)]
+        macro_rules! $name {
+            (@__tgtfeat_dispatch_arch_set $kind [$d($d callback: tt)+] ($d($d args: tt)*)) => {
+                $d($d callback)+! { $d($d args)* [$($member),+] }
+            };
+            (@__tgtfeat_dispatch_arch_set $d other: ident $d($d rest: tt)*) => {
+                compile_error!(concat!(
+                    "`", stringify!($name), "` is not a ", stringify!($d other),
+                    " (defined as a ", stringify!($kind), ")"
+                ));
+            };
+            (@__tgtfeat_dispatch_feature_set $d($d rest: tt)*) => {
+                compile_error! {
+                    concat!("`", stringify!($name), "` is not a feature set (defined as a ", stringify!($kind), ")")
+                }
+            };
+        }
+        #[allow(unused_imports)]
+        $vis use $name;
+    };
+
+    /*
+        Definition of a user-defined feature set
+        (used by `define_feature_set`).
+        The defined macro passes the parenthesized condition to the callback:
+        ( CONDITION ) TOKENS
+    */
+    (
+        @__tgtfeat_dispatch_define_feature_set ($d: tt) ($(#[$attr: meta])*) (pub) $name: ident
+        ($($cond: tt)+)
+    ) => {
+        $(#[$attr])*
+        #[macro_export]
+        macro_rules! $name {
+            (@__tgtfeat_dispatch_feature_set [$d($d callback: tt)+] ($d($d args: tt)*) $d($d rest: tt)*) => {
+                $d($d callback)+!($d($d args)* ($($cond)+) $d($d rest)*)
+            };
+            (@__tgtfeat_dispatch_arch_set $d kind: ident $d($d rest: tt)*) => {
+                compile_error! {
+                    concat!("`", stringify!($name), "` is not a ", stringify!($d kind), " (defined as a feature set)")
+                }
+            };
+        }
+    };
+    (
+        @__tgtfeat_dispatch_define_feature_set ($d: tt) ($(#[$attr: meta])*) ($vis: vis) $name: ident
+        ($($cond: tt)+)
+    ) => {
+        $(#[$attr])*
+        #[allow(unused_macros)]
+        macro_rules! $name {
+            (@__tgtfeat_dispatch_feature_set [$d($d callback: tt)+] ($d($d args: tt)*) $d($d rest: tt)*) => {
+                $d($d callback)+!($d($d args)* ($($cond)+) $d($d rest)*)
+            };
+            (@__tgtfeat_dispatch_arch_set $d kind: ident $d($d rest: tt)*) => {
+                compile_error! {
+                    concat!("`", stringify!($name), "` is not a ", stringify!($d kind), " (defined as a feature set)")
+                }
+            };
+        }
+        #[allow(unused_imports)]
+        $vis use $name;
+    };
+
+    /*
+        Architecture-specific `if`-`else` chain.
+        Note that families are also parsed in @__tgtfeat_dispatch_arch_chain_2.
+    */
+    // `#[cfg]` on an architecture clause (the clause is removed if not configured).
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) #[cfg($meta: meta)] $(#[cfg($meta2: meta)])* $if: tt $($rest: tt)*) => {
+        {
+            #[cfg(all($meta $(, $meta2)*))]
+            {
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $if $($rest)*
+                )
+            }
+            #[cfg(not(all($meta $(, $meta2)*)))]
+            {
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: family("aarch64") → any(target_arch = "aarch64", target_arch = "arm64ec")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((family("aarch64")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (family("aarch64")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any(target_arch = "aarch64", target_arch = "arm64ec")))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (family("aarch64")) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: family("riscv") → any(target_arch = "riscv32", target_arch = "riscv64")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((family("riscv")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (family("riscv")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (family("riscv")) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: family("x86") → any(target_arch = "x86", target_arch = "x86_64")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((family("x86")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (family("x86")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (family("x86")) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: family("arm") → any(target_arch = "aarch64", target_arch = "arm64ec", target_arch = "arm")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((family("arm")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec", target_arch = "arm"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (family("arm")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any(target_arch = "aarch64", target_arch = "arm64ec", target_arch = "arm")))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (family("arm")) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: family("mips") → any(target_arch = "mips", target_arch = "mips64")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((family("mips")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "mips", target_arch = "mips64"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (family("mips")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (family("mips")) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: family("powerpc") → any(target_arch = "powerpc", target_arch = "powerpc64")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((family("powerpc")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (family("powerpc")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any(target_arch = "powerpc", target_arch = "powerpc64")))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (family("powerpc")) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: class("arm") → any(target_arch = "aarch64", target_arch = "arm64ec", target_arch = "arm")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((class("arm")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec", target_arch = "arm"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (class("arm")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any(target_arch = "aarch64", target_arch = "arm64ec", target_arch = "arm")))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (class("arm")) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: class("mips") → any(target_arch = "mips", target_arch = "mips64", target_arch = "mips32r6", target_arch = "mips64r6")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((class("mips")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "mips", target_arch = "mips64", target_arch = "mips32r6", target_arch = "mips64r6"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (class("mips")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any(target_arch = "mips", target_arch = "mips64", target_arch = "mips32r6", target_arch = "mips64r6")))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (class("mips")) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: class("mips-classic") → any(target_arch = "mips", target_arch = "mips64")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((class("mips-classic")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "mips", target_arch = "mips64"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (class("mips-classic")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (class("mips-classic")) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: class("mipsr6") → any(target_arch = "mips32r6", target_arch = "mips64r6")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((class("mipsr6")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "mips32r6", target_arch = "mips64r6"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (class("mipsr6")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
             }
             #[cfg(not(any(target_arch = "mips32r6", target_arch = "mips64r6")))]
             {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (class("mipsr6")) ($($if)*));
                 $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
                     ($($else)*) $($rest)*
                 )
             }
         }
     };
     // `if`: class("powerpc") → any(target_arch = "powerpc", target_arch = "powerpc64")
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) ((class("powerpc")) ($($if: tt)*)) $($rest: tt)*) => {
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((class("powerpc")) ($($if: tt)*)) $($rest: tt)*) => {
         {
             #[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
             {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
                 $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause (class("powerpc")) ($($opts),*)
+                    @__tgtfeat_dispatch_arch_clause (class("powerpc")) ($($opts)*)
                     ($($else)*) ($($if)*)
                 )
             }
             #[cfg(not(any(target_arch = "powerpc", target_arch = "powerpc64")))]
             {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (class("powerpc")) ($($if)*));
                 $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
                     ($($else)*) $($rest)*
                 )
             }
         }
     };
     // `if`: class("sparc") → any(target_arch = "sparc", target_arch = "sparc64")
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) ((class("sparc")) ($($if: tt)*)) $($rest: tt)*) => {
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((class("sparc")) ($($if: tt)*)) $($rest: tt)*) => {
         {
             #[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
             {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
                 $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause (class("sparc")) ($($opts),*)
+                    @__tgtfeat_dispatch_arch_clause (class("sparc")) ($($opts)*)
                     ($($else)*) ($($if)*)
                 )
             }
             #[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
             {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (class("sparc")) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: class("wasm") → any(target_arch = "wasm32", target_arch = "wasm64")
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) ((class("wasm")) ($($if: tt)*)) $($rest: tt)*) => {
+        {
+            #[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause (class("wasm")) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any(target_arch = "wasm32", target_arch = "wasm64")))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check (class("wasm")) ($($if)*));
                 $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
                     ($($else)*) $($rest)*
                 )
             }
         }
     };
-    // `if`: class("wasm") → any(target_arch = "wasm32", target_arch = "wasm64")
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) ((class("wasm")) ($($if: tt)*)) $($rest: tt)*) => {
-        {
-            #[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause (class("wasm")) ($($opts),*)
-                    ($($else)*) ($($if)*)
-                )
+    // `if`: User-defined family / class (query the architectures).
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) (($kind: ident($name: ident)) ($($if: tt)*)) $($rest: tt)*) => {
+        $name! {
+            @__tgtfeat_dispatch_arch_set $kind [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_arch_chain_user ($kind($name)) ($($opts)*) ($($else)*) ($($if)*) ($($rest)*))
+        }
+    };
+    (
+        @__tgtfeat_dispatch_arch_chain_user ($kind: ident($name: ident)) ($($opts: tt)*) ($($else: tt)*) ($($if: tt)*) ($($rest: tt)*)
+        [$($member: tt),+]
+    ) => {
+        {
+            #[cfg(any($(target_arch = $member),+))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause ($kind([$name: $($member),+])) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any($(target_arch = $member),+)))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check ($kind([$name: $($member),+])) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+    // `if`: Generic (others): pass to the final step below.
+    (@__tgtfeat_dispatch_arch_chain ($($opts: tt)*) ($($else: tt)*) (($($arch: tt $(($arch_arg: tt))?)||+) ($($if: tt)*)) $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (() ($($arch$(($arch_arg))?)||+) ($($if)*)) $($rest)*
+        )
+    };
+    // `else`
+    (@__tgtfeat_dispatch_arch_chain ($dyn: meta, $nightly: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) ($($else: tt)*)) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_single ($all, $query) $($else)*)
+    };
+
+    /*
+        Architecture-specific `if`-`else` chain: `if` (final step)
+        Conversion to regular list of architectures.
+    */
+    // family("aarch64") → "aarch64" || "arm64ec"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (family("aarch64") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "aarch64", "arm64ec",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // family("riscv") → "riscv32" || "riscv64"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (family("riscv") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "riscv32", "riscv64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // family("x86") → "x86" || "x86_64"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (family("x86") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "x86", "x86_64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // family("arm") → "aarch64" || "arm64ec" || "arm"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (family("arm") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "aarch64", "arm64ec", "arm",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // family("mips") → "mips" || "mips64"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (family("mips") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "mips", "mips64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // family("powerpc") → "powerpc" || "powerpc64"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (family("powerpc") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "powerpc", "powerpc64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // class("arm") → "aarch64" || "arm64ec" || "arm"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (class("arm") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "aarch64", "arm64ec", "arm",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // class("mips") → "mips" || "mips64" || "mips32r6" || "mips64r6"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (class("mips") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "mips", "mips64", "mips32r6", "mips64r6",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // class("mips-classic") → "mips" || "mips64"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (class("mips-classic") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "mips", "mips64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // class("mipsr6") → "mips32r6" || "mips64r6"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (class("mipsr6") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "mips32r6", "mips64r6",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // class("powerpc") → "powerpc" || "powerpc64"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (class("powerpc") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "powerpc", "powerpc64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // class("sparc") → "sparc" || "sparc64"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (class("sparc") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "sparc", "sparc64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // class("wasm") → "wasm32" || "wasm64"
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) (class("wasm") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* "wasm32", "wasm64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // User-defined family / class (query the architectures).
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) ($kind: ident($name: ident) $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $name! {
+            @__tgtfeat_dispatch_arch_set $kind [$crate::target_feature_dispatch]
+            (
+                @__tgtfeat_dispatch_arch_chain_2_user ($($opts)*) ($($else)*)
+                ($($added,)*) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*) ($($rest)*)
+            )
+        }
+    };
+    (
+        @__tgtfeat_dispatch_arch_chain_2_user ($($opts: tt)*) ($($else: tt)*)
+        ($($added: tt,)*) ($($arch2: tt)*) ($($if: tt)*) ($($rest: tt)*) [$($member: tt),+]
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* $($member,)+) ($($arch2)*) ($($if)*))
+            $($rest)*
+        )
+    };
+    // Others
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) ($arch1: tt ($arch1_arg: tt) $(|| $($arch2: tt$(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        compile_error!(concat!("Invalid architecture specifier: ", stringify!($arch1($arch1_arg))));
+    };
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)*) ($arch1: tt $(|| $($arch2: tt$(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_chain_2 ($($opts)*) ($($else)*)
+            (($($added,)* $arch1,) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            $($rest)*
+        )
+    };
+    // Architectural branch conversion is completed.
+    (
+        @__tgtfeat_dispatch_arch_chain_2 ($($opts: tt)*) ($($else: tt)*)
+        (($($added: tt,)+) () ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        {
+            #[cfg(any($(target_arch = $added),+))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_clause ($($added)||+) ($($opts)*)
+                    ($($else)*) ($($if)*)
+                )
+            }
+            #[cfg(not(any($(target_arch = $added),+)))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check ($($added)||+) ($($if)*));
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_arch_chain ($($opts)*)
+                    ($($else)*) $($rest)*
+                )
+            }
+        }
+    };
+
+    /*
+        Architecture-specific clause (one of architectural `if`).
+
+        If the `else` clause is missing, fallback for static dispatching is of
+        the root and fallback for dynamic dispatching is none
+        (meaning, do the "last resort" static dispatching as a fallback).
+
+        If the `else` clause exists, it is always used as the final fallback
+        (for both static and dynamic dispatching methods).
+
+        If one of the following is specified:
+
+        1.  ( EXPR )
+        2.  { /* statements (results in an expression) */ }
+        3.  (empty)
+
+        This is an architecture-only dispatch.
+    */
+    // `if`-`else` chain (simple: all conditions are conjunctions of features).
+    (
+        @__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: tt)*) ($($else1: tt)*)
+        ($(if $($feat: tt)&&+ { $($if: tt)* })else+ $(else { $($else2: tt)* })?)
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_simple
+            ((($($arch$(($arch_arg))?)||+) ($($opts)*) ($($else1)*)) ($(if $($feat)&&+ { $($if)* })else+ $(else { $($else2)* })?))
+            ($(else { $($else2)* })?)
+            ($((($($feat)&&+) ($($if)*)))+)
+            $(($($feat)&&+))+
+        )
+    };
+    // `if`-`else` chain (generic).
+    (
+        @__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: tt)*) ($($else1: tt)*)
+        ($(#[$($attr: tt)+])* if $($rest: tt)+)
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse
+            (($($arch$(($arch_arg))?)||+) ($($opts)*) ($($else1)*)) ()
+            $(#[$($attr)+])* if $($rest)+
+        )
+    };
+    // Architecture-only dispatch: Single expression enclosed by parens.
+    (@__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($dyn: meta, $nightly: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) ($($else1: tt)*) (($expr: expr))) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_single ($all, $query) $expr)
+    };
+    // Architecture-only dispatch: Single block results in an expression.
+    (@__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($dyn: meta, $nightly: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) ($($else1: tt)*) ({$($tt: tt)*})) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_single ($all, $query) $($tt)*)
+    };
+    // Architecture-only dispatch: Empty (handle as returning the unit value).
+    (@__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($dyn: meta, $nightly: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) ($($else1: tt)*) ()) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_single ($all, $query) ())
+    };
+    // Invalid architecture clause.
+    (@__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: tt)*) ($($else1: tt)*) ($($tt: tt)*)) => {
+        compile_error!("unsupported or invalid architecture clause");
+    };
+    /*
+        Validation of an architecture-specific clause which is not
+        configured on the target architecture.
+
+        Feature-specific clauses are parsed as usual (with the marker
+        `@__tgtfeat_dispatch_check_only` in place of the fallback) but
+        only feature names and unreachable clauses are checked
+        (nothing is evaluated).
+    */
+    (@__tgtfeat_dispatch_arch_clause_check ($($arch: tt $(($arch_arg: tt))?)||+) ($(#[$($attr: tt)+])* if $($rest: tt)+)) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_arch_clause ($($arch$(($arch_arg))?)||+)
+            (any(), any(), any(), any(), any(), any(), any(), [])
+            (@__tgtfeat_dispatch_check_only) ($(#[$($attr)+])* if $($rest)+)
+        )
+    };
+    (@__tgtfeat_dispatch_arch_clause_check $archs: tt ($($tt: tt)*)) => {};
+    // Validation of the rest of the chain (after the configured clause).
+    (@__tgtfeat_dispatch_arch_chain_check #[cfg($meta: meta)] $(#[cfg($meta2: meta)])* $if: tt $($rest: tt)*) => {
+        #[cfg(all($meta $(, $meta2)*))]
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $if);
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+    };
+    (@__tgtfeat_dispatch_arch_chain_check (($kind: ident($name: ident)) ($($if: tt)*)) $($rest: tt)*) => {
+        $name! {
+            @__tgtfeat_dispatch_arch_set $kind [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_arch_chain_check_user ($kind($name)) ($($if)*))
+        }
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+    };
+    (@__tgtfeat_dispatch_arch_chain_check (($($arch: tt $(($arch_arg: tt))?)||+) ($($if: tt)*)) $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check ($($arch$(($arch_arg))?)||+) ($($if)*));
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_chain_check $($rest)*);
+    };
+    (@__tgtfeat_dispatch_arch_chain_check) => {};
+    (@__tgtfeat_dispatch_arch_chain_check_user ($kind: ident($name: ident)) ($($if: tt)*) [$($member: tt),+]) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_arch_clause_check ($kind([$name: $($member),+])) ($($if)*));
+    };
+
+    /*
+        Feature-specific `if`-`else` chain (parser).
+
+        Each feature-specific clause is converted to the following form:
+
+        ((STATIC_CFG) (COND) (BODY))
+
+        where STATIC_CFG is a configuration predicate used on static
+        dispatching and COND is the original condition (used on
+        dynamic dispatching; see "Feature condition (dynamic dispatching)").
+
+        If all conditions are conjunctions of features (the most common case),
+        the whole chain is converted at once.  Otherwise, each clause is
+        parsed one by one.
+    */
+    // Simple chain: all features are literals.
+    (
+        @__tgtfeat_dispatch_feat_simple (($($ctx: tt)*) $_chain: tt)
+        ($(else { $($else2: tt)* })?)
+        ($((($($feat: tt)&&+) ($($if: tt)*)))+)
+        $(($($_: literal)&&+))+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse_end ($($ctx)*)
+            ($(((all($(target_feature = $feat),+)) ($($feat)&&+) ($($if)*)))+)
+            $(else { $($else2)* })?
+        )
+    };
+    // Simple chain: not all features are literals (parse as generic).
+    (@__tgtfeat_dispatch_feat_simple (($($ctx: tt)*) ($($chain: tt)*)) $($_: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_parse ($($ctx)*) () $($chain)*)
+    };
+    // `#[cfg]` and `#[label]` on a feature-specific clause
+    // (the clause is marked and resolved in @__tgtfeat_dispatch_feat_cfg).
+    (
+        @__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) ()
+        #[cfg($meta: meta)] $(#[cfg($meta2: meta)])* $(#[label = $label: literal])? if $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse ($($ctx)*)
+            (#[cfg(all($meta $(, $meta2)*))] $(#[label = $label])?) else if $($rest)+
+        )
+    };
+    (
+        @__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) ($($clauses: tt)+)
+        else #[cfg($meta: meta)] $(#[cfg($meta2: meta)])* $(#[label = $label: literal])? if $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse ($($ctx)*)
+            ($($clauses)+ #[cfg(all($meta $(, $meta2)*))] $(#[label = $label])?) else if $($rest)+
+        )
+    };
+    (
+        @__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) ()
+        #[label = $label: literal] $(#[cfg($meta: meta)])* if $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse ($($ctx)*)
+            (#[cfg(all($($meta),*))] #[label = $label]) else if $($rest)+
+        )
+    };
+    (
+        @__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) ($($clauses: tt)+)
+        else #[label = $label: literal] $(#[cfg($meta: meta)])* if $($rest: tt)+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse ($($ctx)*)
+            ($($clauses)+ #[cfg(all($($meta),*))] #[label = $label]) else if $($rest)+
+        )
+    };
+    // `if` or `else if` with a proof token (bound at the start of the body).
+    (
+        @__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) ()
+        if $($feat: tt)&&+ as $token: ident { $($if: tt)* } $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse ($($ctx)*) ()
+            if $($feat)&&+ {
+                let $token = $crate::target_feature_dispatch!(@__tgtfeat_dispatch_token $($feat)&&+);
+                $($if)*
+            }
+            $($rest)*
+        )
+    };
+    (
+        @__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) ($($clauses: tt)+)
+        else if $($feat: tt)&&+ as $token: ident { $($if: tt)* } $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse ($($ctx)*) ($($clauses)+)
+            else if $($feat)&&+ {
+                let $token = $crate::target_feature_dispatch!(@__tgtfeat_dispatch_token $($feat)&&+);
+                $($if)*
+            }
+            $($rest)*
+        )
+    };
+    // `if` or `else if` (simple: features, `&&` and `||` only).
+    (@__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) () if $($($feat: tt)&&+)||+ { $($if: tt)* } $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse_simple ($($ctx)*) () ($($if)*) ($($rest)*)
+            ($($($feat)&&+)||+) $(($($feat)&&+))||+
+        )
+    };
+    (@__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) ($($clauses: tt)+) else if $($($feat: tt)&&+)||+ { $($if: tt)* } $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse_simple ($($ctx)*) ($($clauses)+) ($($if)*) ($($rest)*)
+            ($($($feat)&&+)||+) $(($($feat)&&+))||+
+        )
+    };
+    // `if` or `else if` (generic).
+    (@__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) ($($clauses: tt)*) $(else)? if $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond
+            [{ @__tgtfeat_dispatch_feat_parse_next ($($ctx)*) ($($clauses)*) }]
+            () () () () $($rest)+
+        )
+    };
+    // End of the chain.
+    (@__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) ($($clauses: tt)+) $(else { $($else2: tt)* })?) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_cfg ($($ctx)*) () ($($clauses)+) $(else { $($else2)* })?)
+    };
+    // Invalid tokens after a feature-specific clause.
+    (@__tgtfeat_dispatch_feat_parse ($($ctx: tt)*) ($($clauses: tt)*) $($rest: tt)*) => {
+        compile_error!(concat!("invalid feature-specific clause: ", stringify!($($rest)*)));
+    };
+    // Simple clause: conjunction of features.
+    (
+        @__tgtfeat_dispatch_feat_parse_simple ($($ctx: tt)*) ($($clauses: tt)*) ($($if: tt)*) ($($rest: tt)*)
+        ($($cond: tt)*) ($($feat: tt)&&+)
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse_simple_2 ($($ctx)*) ($($clauses)*) ($($if)*) ($($rest)*)
+            ($($cond)*) (all($(target_feature = $feat),+)) $($feat)&&+
+        )
+    };
+    // Simple clause: disjunction of conjunctions of features.
+    (
+        @__tgtfeat_dispatch_feat_parse_simple ($($ctx: tt)*) ($($clauses: tt)*) ($($if: tt)*) ($($rest: tt)*)
+        ($($cond: tt)*) $(($($feat: tt)&&+))||+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse_simple_2 ($($ctx)*) ($($clauses)*) ($($if)*) ($($rest)*)
+            ($($cond)*) (any($(all($(target_feature = $feat),+)),+)) $($($feat)&&+)||+
+        )
+    };
+    // Simple clause: all features are literals.
+    (
+        @__tgtfeat_dispatch_feat_parse_simple_2 ($($ctx: tt)*) ($($clauses: tt)*) ($($if: tt)*) ($($rest: tt)*)
+        ($($cond: tt)*) ($($cfg: tt)*) $($($_lit: literal)&&+)||+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse ($($ctx)*)
+            ($($clauses)* (($($cfg)*) ($($cond)*) ($($if)*)))
+            $($rest)*
+        )
+    };
+    // Simple clause: not all features are literals (parse as generic).
+    (
+        @__tgtfeat_dispatch_feat_parse_simple_2 ($($ctx: tt)*) ($($clauses: tt)*) ($($if: tt)*) ($($rest: tt)*)
+        ($($cond: tt)*) ($($_cfg: tt)*) $($_feat: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond
+            [{ @__tgtfeat_dispatch_feat_parse_next ($($ctx)*) ($($clauses)*) }]
+            () () () () $($cond)* { $($if)* } $($rest)*
+        )
+    };
+    // Clause is parsed (continue parsing the rest).
+    (
+        @__tgtfeat_dispatch_feat_parse_next ($($ctx: tt)*) ($($clauses: tt)*)
+        ($($cfg: tt)*) ($($cond: tt)*) { $($if: tt)* } $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_parse ($($ctx)*)
+            ($($clauses)* (($($cfg)*) ($($cond)*) ($($if)*)))
+            $($rest)*
+        )
+    };
+    (
+        @__tgtfeat_dispatch_feat_parse_next ($($ctx: tt)*) ($($clauses: tt)*)
+        ($($cfg: tt)*) ($($cond: tt)*) $next: tt $($rest: tt)*
+    ) => {
+        compile_error!(concat!("invalid feature condition: ", stringify!($($cond)* $next)));
+    };
+    // Clause with `#[cfg]` (the clause is removed if not configured).
+    (
+        @__tgtfeat_dispatch_feat_cfg ($($ctx: tt)*) ($($clauses: tt)*)
+        (#[cfg($meta: meta)] $(#[label = $label: literal])? ($($clause: tt)*) $($rest: tt)*) $($else: tt)*
+    ) => {
+        {
+            #[cfg($meta)]
+            {
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_feat_cfg ($($ctx)*) ($($clauses)*)
+                    ($(#[label = $label])? ($($clause)*) $($rest)*) $($else)*
+                )
+            }
+            #[cfg(not($meta))]
+            {
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_feat_cfg ($($ctx)*) ($($clauses)*) ($($rest)*) $($else)*
+                )
+            }
+        }
+    };
+    // Clause with `#[label]` (the label is attached to the body).
+    (
+        @__tgtfeat_dispatch_feat_cfg ($($ctx: tt)*) ($($clauses: tt)*)
+        (#[label = $label: literal] (($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)) $($rest: tt)*) $($else: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_cfg ($($ctx)*)
+            ($($clauses)* (($($cfg)*) ($($cond)*) (@__tgtfeat_dispatch_labeled ($label) $($if)*)))
+            ($($rest)*) $($else)*
+        )
+    };
+    // Clause without `#[cfg]`.
+    (@__tgtfeat_dispatch_feat_cfg ($($ctx: tt)*) ($($clauses: tt)*) ($clause: tt $($rest: tt)*) $($else: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_cfg ($($ctx)*) ($($clauses)* $clause) ($($rest)*) $($else)*
+        )
+    };
+    // All clauses are resolved.
+    (@__tgtfeat_dispatch_feat_cfg ($($ctx: tt)*) ($($clauses: tt)+) () $($else: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_parse_end ($($ctx)*) ($($clauses)+) $($else)*)
+    };
+    // All clauses are removed (validation only).
+    (@__tgtfeat_dispatch_feat_cfg ($archs: tt $opts: tt (@__tgtfeat_dispatch_check_only)) () () $($else: tt)*) => { {} };
+    // All clauses are removed: `else` or the fallback of the root.
+    (@__tgtfeat_dispatch_feat_cfg ($($ctx: tt)*) () () else { @__tgtfeat_dispatch_no_fallback }) => {
+        compile_error!("invalid feature-specific `else` clause");
+    };
+    (@__tgtfeat_dispatch_feat_cfg ($archs: tt ($dyn: meta, $nightly: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) ($($else1: tt)*)) () () else { $($else2: tt)* }) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_single ($all, $query) $($else2)*)
+    };
+    (@__tgtfeat_dispatch_feat_cfg ($archs: tt ($dyn: meta, $nightly: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) ($($else1: tt)*)) () ()) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_single ($all, $query) $($else1)*)
+    };
+    // End: validation only.
+    (
+        @__tgtfeat_dispatch_feat_parse_end
+        (($($arch: tt $(($arch_arg: tt))?)||+) $opts: tt (@__tgtfeat_dispatch_check_only))
+        ($((($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)))+) $($else: tt)*
+    ) => {
+        {
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_check_clauses [$($arch$(($arch_arg))?)||+]
+                $((($($cfg)*) ($($cond)*) ($($if)*)))+
+            );
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_check_reach [$($arch$(($arch_arg))?)||+]
+                $((($($cfg)*) ($($cond)*) ($($if)*)))+
+            );
+        }
+    };
+    // End: `else` (invalid).
+    (@__tgtfeat_dispatch_feat_parse_end ($($ctx: tt)*) ($($clauses: tt)+) else { @__tgtfeat_dispatch_no_fallback }) => {
+        compile_error!("invalid feature-specific `else` clause");
+    };
+    // End: `else` (pass to the entrypoint).
+    (
+        @__tgtfeat_dispatch_feat_parse_end
+        (($($arch: tt $(($arch_arg: tt))?)||+) ($dyn: meta, $nightly: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) ($($else1: tt)*))
+        ($((($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)))+)
+        else { $($else2: tt)* }
+    ) => {
+        {
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_check_clauses [$($arch$(($arch_arg))?)||+]
+                $((($($cfg)*) ($($cond)*) ($($if)*)))+
+            );
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_check_reach [$($arch$(($arch_arg))?)||+]
+                $((($($cfg)*) ($($cond)*) ($($if)*)))+
+            );
+            #[cfg($verify)]
+            $crate::__tgtfeat_dispatch_verify_static! { $(($($cond)*))+ }
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_feat_chain_entry
+                ($($arch$(($arch_arg))?)||+) ($dyn, $nightly, $cached, $all) [$($det)*]
+                ($crate::target_feature_dispatch!(@__tgtfeat_dispatch_fallback_body ($all, $query) $($else2)*))
+                ($crate::target_feature_dispatch!(@__tgtfeat_dispatch_fallback_body ($all, $query) $($else2)*))
+                $((
+                    ($($cfg)*) ($($cond)*)
+                    ($crate::target_feature_dispatch!(@__tgtfeat_dispatch_clause_body ($enable, $all, $query) ($($cond)*) $($if)*))
+                ))+
+            )
+        }
+    };
+    // End: No `else` (pass to the entrypoint).
+    (
+        @__tgtfeat_dispatch_feat_parse_end
+        (($($arch: tt $(($arch_arg: tt))?)||+) ($dyn: meta, $nightly: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*]) ($($else1: tt)*))
+        ($((($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)))+)
+    ) => {
+        {
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_check_clauses [$($arch$(($arch_arg))?)||+]
+                $((($($cfg)*) ($($cond)*) ($($if)*)))+
+            );
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_check_reach [$($arch$(($arch_arg))?)||+]
+                $((($($cfg)*) ($($cond)*) ($($if)*)))+
+            );
+            #[cfg($verify)]
+            $crate::__tgtfeat_dispatch_verify_static! { $(($($cond)*))+ }
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_feat_chain_entry
+                ($($arch$(($arch_arg))?)||+) ($dyn, $nightly, $cached, $all) [$($det)*]
+                ($crate::target_feature_dispatch!(@__tgtfeat_dispatch_fallback_body ($all, $query) $($else1)*))
+                (@__tgtfeat_dispatch_no_fallback)
+                $((
+                    ($($cfg)*) ($($cond)*)
+                    ($crate::target_feature_dispatch!(@__tgtfeat_dispatch_clause_body ($enable, $all, $query) ($($cond)*) $($if)*))
+                ))+
+            )
+        }
+    };
+
+    /*
+        Validation of feature names.
+
+        Each feature (string literal) in the conditions is checked against
+        the tables of known features of the architectures in the constant
+        context (an unknown feature causes a compilation error).
+        Nested parentheses and target features in statically checked
+        conditions (such as mandatory extensions of RISC-V profiles)
+        are checked recursively and other tokens (operators and
+        unchecked features) are ignored.
+    */
+    (@__tgtfeat_dispatch_check_clauses $archs: tt $((($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)))+) => {
+        $($crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_cond $archs $($cond)*);)+
+    };
+    (@__tgtfeat_dispatch_check_cond $archs: tt $($token: tt)*) => {
+        $($crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_token $archs $token);)*
+    };
+    (@__tgtfeat_dispatch_check_token [$kind: ident([$name: ident: $($member: tt),+])] $feat: literal) => {
+        const _: () = $crate::__private::features::check(
+            concat!(stringify!($kind), "(", stringify!($name), ")"),
+            &[$($crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_tables $member)),+],
+            $feat,
+        );
+    };
+    (@__tgtfeat_dispatch_check_token [$($arch: tt $(($arch_arg: tt))?)||+] $feat: literal) => {
+        const _: () = $crate::__private::features::check(
+            stringify!($($arch$(($arch_arg))?)||+),
+            &[$($crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_tables $arch$(($arch_arg))?)),+],
+            $feat,
+        );
+    };
+    (@__tgtfeat_dispatch_check_token $archs: tt ($($inner: tt)*)) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_cond $archs $($inner)*);
+    };
+    (@__tgtfeat_dispatch_check_token $archs: tt {static($($pred: tt)*)}) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_static $archs $($pred)*);
+    };
+    (@__tgtfeat_dispatch_check_token $archs: tt $token: tt) => {};
+    // Features in statically checked conditions (predicates).
+    (@__tgtfeat_dispatch_check_static $archs: tt target_feature = $feat: literal $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_token $archs $feat);
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_static $archs $($rest)*);
+    };
+    (@__tgtfeat_dispatch_check_static $archs: tt ($($inner: tt)*) $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_static $archs $($inner)*);
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_static $archs $($rest)*);
+    };
+    (@__tgtfeat_dispatch_check_static $archs: tt $token: tt $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_static $archs $($rest)*);
+    };
+    (@__tgtfeat_dispatch_check_static $archs: tt) => {};
+    // Tables of known features per architecture.
+    (@__tgtfeat_dispatch_check_tables family("aarch64")) => { &[$crate::__private::features::AARCH64] };
+    (@__tgtfeat_dispatch_check_tables family("riscv")) => { &[$crate::__private::features::RISCV] };
+    (@__tgtfeat_dispatch_check_tables family("x86")) => { &[$crate::__private::features::X86] };
+    (@__tgtfeat_dispatch_check_tables family("arm")) => {
+        &[$crate::__private::features::ARM, $crate::__private::features::AARCH64]
+    };
+    (@__tgtfeat_dispatch_check_tables family("mips")) => { &[$crate::__private::features::MIPS] };
+    (@__tgtfeat_dispatch_check_tables family("powerpc")) => { &[$crate::__private::features::POWERPC] };
+    (@__tgtfeat_dispatch_check_tables class("arm")) => {
+        &[$crate::__private::features::ARM, $crate::__private::features::AARCH64]
+    };
+    (@__tgtfeat_dispatch_check_tables class("mips")) => { &[$crate::__private::features::MIPS] };
+    (@__tgtfeat_dispatch_check_tables class("mips-classic")) => { &[$crate::__private::features::MIPS] };
+    (@__tgtfeat_dispatch_check_tables class("mipsr6")) => { &[$crate::__private::features::MIPS] };
+    (@__tgtfeat_dispatch_check_tables class("powerpc")) => { &[$crate::__private::features::POWERPC] };
+    (@__tgtfeat_dispatch_check_tables class("sparc")) => { &[$crate::__private::features::SPARC] };
+    (@__tgtfeat_dispatch_check_tables class("wasm")) => { &[$crate::__private::features::WASM] };
+    (@__tgtfeat_dispatch_check_tables "aarch64") => { &[$crate::__private::features::AARCH64] };
+    (@__tgtfeat_dispatch_check_tables "arm64ec") => { &[$crate::__private::features::AARCH64] };
+    (@__tgtfeat_dispatch_check_tables "arm") => { &[$crate::__private::features::ARM] };
+    (@__tgtfeat_dispatch_check_tables "loongarch32") => { &[$crate::__private::features::LOONGARCH] };
+    (@__tgtfeat_dispatch_check_tables "loongarch64") => { &[$crate::__private::features::LOONGARCH] };
+    (@__tgtfeat_dispatch_check_tables "mips") => { &[$crate::__private::features::MIPS] };
+    (@__tgtfeat_dispatch_check_tables "mips64") => { &[$crate::__private::features::MIPS] };
+    (@__tgtfeat_dispatch_check_tables "mips32r6") => { &[$crate::__private::features::MIPS] };
+    (@__tgtfeat_dispatch_check_tables "mips64r6") => { &[$crate::__private::features::MIPS] };
+    (@__tgtfeat_dispatch_check_tables "powerpc") => { &[$crate::__private::features::POWERPC] };
+    (@__tgtfeat_dispatch_check_tables "powerpc64") => { &[$crate::__private::features::POWERPC] };
+    (@__tgtfeat_dispatch_check_tables "riscv32") => { &[$crate::__private::features::RISCV] };
+    (@__tgtfeat_dispatch_check_tables "riscv64") => { &[$crate::__private::features::RISCV] };
+    (@__tgtfeat_dispatch_check_tables "s390x") => { &[$crate::__private::features::S390X] };
+    (@__tgtfeat_dispatch_check_tables "sparc") => { &[$crate::__private::features::SPARC] };
+    (@__tgtfeat_dispatch_check_tables "sparc64") => { &[$crate::__private::features::SPARC] };
+    (@__tgtfeat_dispatch_check_tables "wasm32") => { &[$crate::__private::features::WASM] };
+    (@__tgtfeat_dispatch_check_tables "wasm64") => { &[$crate::__private::features::WASM] };
+    (@__tgtfeat_dispatch_check_tables "x86") => { &[$crate::__private::features::X86] };
+    (@__tgtfeat_dispatch_check_tables "x86_64") => { &[$crate::__private::features::X86] };
+    // Unknown architecture (skip validation).
+    (@__tgtfeat_dispatch_check_tables $($arch: tt)+) => { &[] };
+
+    /*
+        Detection of unreachable clauses.
+
+        For each pair of clauses, a warning (deprecation) is emitted if
+        the condition of the later clause always implies the condition of
+        the earlier one (considering implications of target features).
+        Only conditions in the disjunctive normal form consisting of
+        (non-negated) features and parenthesized conjunctions are analyzed.
+    */
+    (@__tgtfeat_dispatch_check_reach [$kind: ident([$name: ident: $($member: tt),+])] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_reach [$($member)||+] $($rest)*);
+    };
+    (@__tgtfeat_dispatch_check_reach $archs: tt $first: tt $($rest: tt)*) => {
+        $($crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_reach_pair $archs $first $rest);)*
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_reach $archs $($rest)*);
+    };
+    (@__tgtfeat_dispatch_check_reach $archs: tt) => {};
+    (
+        @__tgtfeat_dispatch_check_reach_pair [$($arch: tt $(($arch_arg: tt))?)||+]
+        (($($cfg1: tt)*) ($($cond1: tt)*) ($($if1: tt)*))
+        (($($cfg2: tt)*) ($($cond2: tt)*) ($($if2: tt)*))
+    ) => {
+        const _: () = {
+            struct UnreachableClause<const UNREACHABLE: bool>;
+            impl UnreachableClause<true> {
+                #[deprecated = concat!(
+                    "feature-specific clause `if ", stringify!($($cond2)*),
+                    "` is unreachable because the preceding clause `if ", stringify!($($cond1)*),
+                    "` always matches first"
+                )]
+                const fn check() {}
             }
-            #[cfg(not(any(target_arch = "wasm32", target_arch = "wasm64")))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
-                    ($($else)*) $($rest)*
-                )
+            impl UnreachableClause<false> {
+                const fn check() {}
             }
-        }
+            UnreachableClause::<{
+                $crate::__private::implications::implies(
+                    &[$($crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_implications $arch$(($arch_arg))?)),+],
+                    $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_dnf $($cond2)*),
+                    $crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_dnf $($cond1)*),
+                )
+            }>::check()
+        };
+    };
+    // Condition in the disjunctive normal form (an empty list if not supported).
+    (@__tgtfeat_dispatch_check_dnf $($($factor: tt)&&+)||+) => {
+        &[$(&[$($crate::target_feature_dispatch!(@__tgtfeat_dispatch_check_dnf_factor $factor)),+]),+]
+    };
+    (@__tgtfeat_dispatch_check_dnf $($token: tt)*) => { &[] };
+    (@__tgtfeat_dispatch_check_dnf_factor $feat: literal) => { &[$feat] };
+    (@__tgtfeat_dispatch_check_dnf_factor ($($feat: literal)&&+)) => { &[$($feat),+] };
+    (@__tgtfeat_dispatch_check_dnf_factor $token: tt) => { &[] };
+    // Tables of implications of features per architecture.
+    (@__tgtfeat_dispatch_check_implications family("aarch64")) => { &[$crate::__private::implications::AARCH64] };
+    (@__tgtfeat_dispatch_check_implications family("riscv")) => { &[$crate::__private::implications::RISCV] };
+    (@__tgtfeat_dispatch_check_implications family("x86")) => { &[$crate::__private::implications::X86] };
+    (@__tgtfeat_dispatch_check_implications family("arm")) => {
+        &[$crate::__private::implications::ARM, $crate::__private::implications::AARCH64]
+    };
+    (@__tgtfeat_dispatch_check_implications family("powerpc")) => { &[$crate::__private::implications::POWERPC] };
+    (@__tgtfeat_dispatch_check_implications class("arm")) => {
+        &[$crate::__private::implications::ARM, $crate::__private::implications::AARCH64]
+    };
+    (@__tgtfeat_dispatch_check_implications class("powerpc")) => { &[$crate::__private::implications::POWERPC] };
+    (@__tgtfeat_dispatch_check_implications class("wasm")) => { &[$crate::__private::implications::WASM] };
+    (@__tgtfeat_dispatch_check_implications "aarch64") => { &[$crate::__private::implications::AARCH64] };
+    (@__tgtfeat_dispatch_check_implications "arm64ec") => { &[$crate::__private::implications::AARCH64] };
+    (@__tgtfeat_dispatch_check_implications "arm") => { &[$crate::__private::implications::ARM] };
+    (@__tgtfeat_dispatch_check_implications "loongarch32") => { &[$crate::__private::implications::LOONGARCH] };
+    (@__tgtfeat_dispatch_check_implications "loongarch64") => { &[$crate::__private::implications::LOONGARCH] };
+    (@__tgtfeat_dispatch_check_implications "powerpc") => { &[$crate::__private::implications::POWERPC] };
+    (@__tgtfeat_dispatch_check_implications "powerpc64") => { &[$crate::__private::implications::POWERPC] };
+    (@__tgtfeat_dispatch_check_implications "riscv32") => { &[$crate::__private::implications::RISCV] };
+    (@__tgtfeat_dispatch_check_implications "riscv64") => { &[$crate::__private::implications::RISCV] };
+    (@__tgtfeat_dispatch_check_implications "s390x") => { &[$crate::__private::implications::S390X] };
+    (@__tgtfeat_dispatch_check_implications "wasm32") => { &[$crate::__private::implications::WASM] };
+    (@__tgtfeat_dispatch_check_implications "wasm64") => { &[$crate::__private::implications::WASM] };
+    (@__tgtfeat_dispatch_check_implications "x86") => { &[$crate::__private::implications::X86] };
+    (@__tgtfeat_dispatch_check_implications "x86_64") => { &[$crate::__private::implications::X86] };
+    // Others (no implications are known).
+    (@__tgtfeat_dispatch_check_implications $($arch: tt)+) => { &[] };
+
+    /*
+        Feature condition (generic).
+
+        The condition is a boolean expression consisting of
+        feature names (string literals), `&&`, `||`, `!` and parentheses.
+        As in Rust, `!` binds tighter than `&&` and `&&` binds tighter than `||`.
+
+        It is converted to a configuration predicate (using `all`, `any`
+        and `not`) for static dispatching in the continuation-passing style:
+
+        @__tgtfeat_dispatch_cond     [STACK] (COND) (TERMS) (FACTORS) (NEG) TOKENS
+        @__tgtfeat_dispatch_cond_op  [STACK] (COND) (TERMS) (FACTORS) TOKENS
+        @__tgtfeat_dispatch_cond_end [STACK] (COND) (TERMS) (FACTORS) TOKENS
+        @__tgtfeat_dispatch_cond_ret [STACK] (COND) (PREDICATE) TOKENS
+
+        *   STACK:   Frames of the outer parenthesized expressions
+                     followed by the callback `{ ... }`.
+        *   COND:    Parsed condition (as-is; used on dynamic dispatching).
+        *   TERMS:   Predicates of parsed terms (operands of `||`).
+        *   FACTORS: Predicates of parsed factors of the current term
+                     (operands of `&&`).
+        *   NEG:     `!` if the next factor is negated.
+
+        The parser consumes a factor and the following operator at once
+        to save the recursion depth.  On completion, the callback is invoked
+        with the predicate, the condition and the rest of the tokens
+        (starting with the body of the clause).
+    */
+    // Factor: Negation.
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) () ! $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond [$($stack)*] ($($cond)*) ($($terms)*) ($($factors)*) (!) $($rest)+)
     };
-    // `if`: Generic (others): pass to the final step below.
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*) (($($arch: tt $(($arch_arg: tt))?)||+) ($($if: tt)*)) $($rest: tt)*) => {
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) (!) ! $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond [$($stack)*] ($($cond)*) ($($terms)*) ($($factors)*) () $($rest)+)
+    };
+    // Factor: Parenthesized conjunction of features (fast path; fall back to the generic path).
+    (
+        @__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        ($($feat: tt)&&+) $($rest: tt)*
+    ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (() ($($arch$(($arch_arg))?)||+) ($($if)*)) $($rest)*
+            @__tgtfeat_dispatch_cond_conj [$($stack)*] ($($cond)*) ($($terms)*) ($($factors)*) ($($neg)?)
+            (($($feat)&&+) $($rest)*) $($feat)&&+
         )
     };
-    // `else`
-    (@__tgtfeat_dispatch_arch_chain ($($opts: meta),*) ($($else: tt)*)) => {
-        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($else)*)
+    (
+        @__tgtfeat_dispatch_cond_conj [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ()
+        (($($feat: tt)&&+) $($rest: tt)*) $($_lit: literal)&&+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond_op [$($stack)*] ($($cond)* ($($feat)&&+)) ($($terms)*)
+            ($($factors)* (all($(target_feature = $feat),+))) $($rest)*
+        )
     };
-
-    /*
-        Architecture-specific `if`-`else` chain: `if` (final step)
-        Conversion to regular list of architectures.
-    */
-    // family("aarch64") → "aarch64" || "arm64ec"
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) (family("aarch64") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond_conj [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) (!)
+        (($($feat: tt)&&+) $($rest: tt)*) $($_lit: literal)&&+
     ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* "aarch64", "arm64ec",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
-            $($rest)*
+            @__tgtfeat_dispatch_cond_op [$($stack)*] ($($cond)* !($($feat)&&+)) ($($terms)*)
+            ($($factors)* (not(all($(target_feature = $feat),+)))) $($rest)*
         )
     };
-    // family("riscv") → "riscv32" || "riscv64"
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) (family("riscv") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond_conj [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        (($($inner: tt)+) $($rest: tt)*) $($_feat: tt)*
     ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* "riscv32", "riscv64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
-            $($rest)*
+            @__tgtfeat_dispatch_cond
+            [(($($cond)*) ($($terms)*) ($($factors)*) ($($neg)?) ($($rest)*)) $($stack)*]
+            () () () () $($inner)+
         )
     };
-    // family("x86") → "x86" || "x86_64"
+    // Factor: Parenthesized expression (push the current state).
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) (family("x86") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        ($($inner: tt)+) $($rest: tt)*
     ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* "x86", "x86_64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
-            $($rest)*
+            @__tgtfeat_dispatch_cond
+            [(($($cond)*) ($($terms)*) ($($factors)*) ($($neg)?) ($($rest)*)) $($stack)*]
+            () () () () $($inner)+
         )
     };
-    // class("arm") → "aarch64" || "arm64ec" || "arm"
+    // Factor: Unchecked feature (not validated against the table of known features).
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) (class("arm") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        unchecked($feat: literal) $($rest: tt)*
     ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* "aarch64", "arm64ec", "arm",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            @__tgtfeat_dispatch_cond_factor [$($stack)*] ($($cond)*) ($($terms)*) ($($factors)*) ($($neg)?)
+            (target_feature = $feat) ({unchecked($feat)})
             $($rest)*
         )
     };
-    // class("mips") → "mips" || "mips64" || "mips32r6" || "mips64r6"
+    // Factor: Named level (replaced with a predicate and a condition).
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) (class("mips") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        level($level: tt) $($rest: tt)*
     ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* "mips", "mips64", "mips32r6", "mips64r6",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            @__tgtfeat_dispatch_level $level
+            [@__tgtfeat_dispatch_cond_factor [$($stack)*] ($($cond)*) ($($terms)*) ($($factors)*) ($($neg)?)]
             $($rest)*
         )
     };
-    // class("mips-classic") → "mips" || "mips64"
+    // Factor: User-defined feature set (replaced with a parenthesized condition).
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) (class("mips-classic") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        set($name: ident) $($rest: tt)*
     ) => {
-        $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* "mips", "mips64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+        $name!(
+            @__tgtfeat_dispatch_feature_set [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_cond [$($stack)*] ($($cond)*) ($($terms)*) ($($factors)*) ($($neg)?))
             $($rest)*
         )
     };
-    // class("mipsr6") → "mips32r6" || "mips64r6"
+    // Factor: Profile (replaced with a predicate and a condition).
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) (class("mipsr6") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        profile($profile: tt) $($rest: tt)*
     ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* "mips32r6", "mips64r6",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            @__tgtfeat_dispatch_profile $profile
+            [@__tgtfeat_dispatch_cond_factor [$($stack)*] ($($cond)*) ($($terms)*) ($($factors)*) ($($neg)?)]
             $($rest)*
         )
     };
-    // class("powerpc") → "powerpc" || "powerpc64"
+    // Factor: Runtime vector length (replaced with a predicate and a condition).
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) (class("powerpc") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        vlen(>= $bits: tt) $($rest: tt)*
     ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* "powerpc", "powerpc64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            @__tgtfeat_dispatch_vlen vlen $bits
+            [@__tgtfeat_dispatch_cond_factor [$($stack)*] ($($cond)*) ($($terms)*) ($($factors)*) ($($neg)?)]
             $($rest)*
         )
     };
-    // class("sparc") → "sparc" || "sparc64"
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) (class("sparc") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        sve_vl(>= $bits: tt) $($rest: tt)*
     ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* "sparc", "sparc64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
+            @__tgtfeat_dispatch_vlen sve_vl $bits
+            [@__tgtfeat_dispatch_cond_factor [$($stack)*] ($($cond)*) ($($terms)*) ($($factors)*) ($($neg)?)]
             $($rest)*
         )
     };
-    // class("wasm") → "wasm32" || "wasm64"
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) (class("wasm") $(|| $($arch2: tt $(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        vlen($($arg: tt)*) $($rest: tt)*
+    ) => {
+        compile_error!(concat!("invalid vector length condition (`>= N` is expected): ", stringify!(vlen($($arg)*))));
+    };
+    (
+        @__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)
+        sve_vl($($arg: tt)*) $($rest: tt)*
+    ) => {
+        compile_error!(concat!("invalid vector length condition (`>= N` is expected): ", stringify!(sve_vl($($arg)*))));
+    };
+    (
+        @__tgtfeat_dispatch_cond_factor [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ()
+        ($($pred: tt)*) ($($inner: tt)*) $($rest: tt)*
     ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* "wasm32", "wasm64",) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
-            $($rest)*
+            @__tgtfeat_dispatch_cond_op [$($stack)*] ($($cond)* ($($inner)*)) ($($terms)*)
+            ($($factors)* ($($pred)*)) $($rest)*
         )
     };
-    // Others
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) ($arch1: tt ($arch1_arg: tt) $(|| $($arch2: tt$(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond_factor [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) (!)
+        ($($pred: tt)*) ($($inner: tt)*) $($rest: tt)*
     ) => {
-        compile_error!(concat!("Invalid architecture specifier: ", stringify!($arch1($arch1_arg))));
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond_op [$($stack)*] ($($cond)* !($($inner)*)) ($($terms)*)
+            ($($factors)* (not($($pred)*))) $($rest)*
+        )
+    };
+    // Factor: Invalid.
+    (@__tgtfeat_dispatch_cond [{ $($callback: tt)* }] () () () () { $($_body: tt)* } $($rest: tt)*) => {
+        compile_error!("feature condition is missing");
+    };
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?) $feat: ident $($rest: tt)*) => {
+        compile_error!(concat!("invalid feature (not a string literal): ", stringify!($feat)));
+    };
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?)) => {
+        compile_error!(concat!("invalid feature condition (a feature is expected): ", stringify!($($cond)* $($neg)?)));
+    };
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?) { $($_body: tt)* } $($rest: tt)*) => {
+        compile_error!(concat!("invalid feature condition (a feature is expected): ", stringify!($($cond)* $($neg)?)));
+    };
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?) && $($rest: tt)*) => {
+        compile_error!(concat!("invalid feature condition (a feature is expected): ", stringify!($($cond)* $($neg)? &&)));
+    };
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) ($($neg: tt)?) || $($rest: tt)*) => {
+        compile_error!(concat!("invalid feature condition (a feature is expected): ", stringify!($($cond)* $($neg)? ||)));
+    };
+    // Factor: Feature followed by `&&`.
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) () $feat: tt && $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond [$($stack)*] ($($cond)* $feat &&) ($($terms)*)
+            ($($factors)* (target_feature = $feat)) () $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) (!) $feat: tt && $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond [$($stack)*] ($($cond)* !$feat &&) ($($terms)*)
+            ($($factors)* (not(target_feature = $feat))) () $($rest)*
+        )
+    };
+    // Factor: Feature followed by `||` (finish the current term).
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($(($($factor: tt)*))*) () $feat: tt || $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond [$($stack)*] ($($cond)* $feat ||)
+            ($($terms)* (all($($($factor)*,)* target_feature = $feat))) () () $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($(($($factor: tt)*))*) (!) $feat: tt || $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond [$($stack)*] ($($cond)* !$feat ||)
+            ($($terms)* (all($($($factor)*,)* not(target_feature = $feat)))) () () $($rest)*
+        )
+    };
+    // Factor: Feature at the end of the expression.
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) () $feat: tt $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond_end [$($stack)*] ($($cond)* $feat) ($($terms)*)
+            ($($factors)* (target_feature = $feat)) $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_cond [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) (!) $feat: tt $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond_end [$($stack)*] ($($cond)* !$feat) ($($terms)*)
+            ($($factors)* (not(target_feature = $feat))) $($rest)*
+        )
+    };
+    // Operator (after a parenthesized expression): `&&`.
+    (@__tgtfeat_dispatch_cond_op [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) && $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond [$($stack)*] ($($cond)* &&) ($($terms)*) ($($factors)*) () $($rest)*)
+    };
+    // Operator (after a parenthesized expression): `||`.
+    (@__tgtfeat_dispatch_cond_op [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($(($($factor: tt)*))+) || $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond [$($stack)*] ($($cond)* ||)
+            ($($terms)* (all($($($factor)*),+))) () () $($rest)*
+        )
     };
+    // Operator (after a parenthesized expression): End of the expression.
+    (@__tgtfeat_dispatch_cond_op [$($stack: tt)*] ($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_end [$($stack)*] ($($cond)*) ($($terms)*) ($($factors)*) $($rest)*)
+    };
+    // End of the expression: Single factor.
+    (@__tgtfeat_dispatch_cond_end [$($stack: tt)*] ($($cond: tt)*) () (($($factor: tt)*)) $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_ret [$($stack)*] ($($cond)*) ($($factor)*) $($rest)*)
+    };
+    // End of the expression: Single term.
+    (@__tgtfeat_dispatch_cond_end [$($stack: tt)*] ($($cond: tt)*) () ($(($($factor: tt)*))+) $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_ret [$($stack)*] ($($cond)*) (all($($($factor)*),+)) $($rest)*)
+    };
+    // End of the expression: Multiple terms.
+    (@__tgtfeat_dispatch_cond_end [$($stack: tt)*] ($($cond: tt)*) ($(($($term: tt)*))+) ($(($($factor: tt)*))+) $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond_ret [$($stack)*] ($($cond)*)
+            (any($($($term)*),+, all($($($factor)*),+))) $($rest)*
+        )
+    };
+    // Return: to the outer expression (as a factor).
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)*) ($arch1: tt $(|| $($arch2: tt$(($arch2_arg: tt))?)||+)?) ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond_ret
+        [(($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) () ($($rest: tt)*)) $($stack: tt)*]
+        ($($inner: tt)*) ($($pred: tt)*)
     ) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_arch_chain_2 ($($opts),*) ($($else)*)
-            (($($added,)* $arch1,) ($($($arch2$(($arch2_arg))?)||+)?) ($($if)*))
-            $($rest)*
+            @__tgtfeat_dispatch_cond_op [$($stack)*] ($($cond)* ($($inner)*))
+            ($($terms)*) ($($factors)* ($($pred)*)) $($rest)*
         )
     };
-    // Architectural branch conversion is completed.
     (
-        @__tgtfeat_dispatch_arch_chain_2 ($($opts: meta),*) ($($else: tt)*)
-        (($($added: tt,)+) () ($($if: tt)*)) $($rest: tt)*
+        @__tgtfeat_dispatch_cond_ret
+        [(($($cond: tt)*) ($($terms: tt)*) ($($factors: tt)*) (!) ($($rest: tt)*)) $($stack: tt)*]
+        ($($inner: tt)*) ($($pred: tt)*)
     ) => {
-        {
-            #[cfg(any($(target_arch = $added),+))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_clause ($($added)||+) ($($opts),*)
-                    ($($else)*) ($($if)*)
-                )
-            }
-            #[cfg(not(any($(target_arch = $added),+)))]
-            {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_arch_chain ($($opts),*)
-                    ($($else)*) $($rest)*
-                )
-            }
-        }
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond_op [$($stack)*] ($($cond)* !($($inner)*))
+            ($($terms)*) ($($factors)* (not($($pred)*))) $($rest)*
+        )
+    };
+    // Return: to the callback.
+    (@__tgtfeat_dispatch_cond_ret [{ $($callback: tt)* }] ($($cond: tt)*) ($($pred: tt)*) $($rest: tt)*) => {
+        $crate::target_feature_dispatch!($($callback)* ($($pred)*) ($($cond)*) $($rest)*)
+    };
+    // Return: Invalid tokens in the parenthesized expression.
+    (@__tgtfeat_dispatch_cond_ret [$($stack: tt)*] ($($cond: tt)*) ($($pred: tt)*) $($rest: tt)+) => {
+        compile_error!(concat!("invalid feature condition: ", stringify!($($cond)* $($rest)+)));
     };
 
     /*
-        Architecture-specific clause (one of architectural `if`).
+        Named levels.
 
-        If the `else` clause is missing, fallback for static dispatching is of
-        the root and fallback for dynamic dispatching is none
-        (meaning, do the "last resort" static dispatching as a fallback).
+        Each level is passed to the callback as a predicate (for static
+        dispatching; all mandatory features known to the compiler) and
+        a parenthesized conjunction of features (for dynamic dispatching;
+        sorted and limited to features detectable at runtime on stable Rust).
+        Each level is derived from the previous level plus its additions
+        (`armv9.N-a` is `armv9-a` plus the additions of `armv8.(N+5)-a`)
+        and levels which add no detectable features are not defined.
+    */
+    // x86 (x86-64 microarchitecture levels)
+    (@__tgtfeat_dispatch_level "x86-64" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "fxsr", target_feature = "sse", target_feature = "sse2"))
+            ("fxsr" && "sse" && "sse2")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "x86-64-v1" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "fxsr", target_feature = "sse", target_feature = "sse2"))
+            ("fxsr" && "sse" && "sse2")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "x86-64-v2" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "cmpxchg16b", target_feature = "fxsr", target_feature = "lahfsahf", target_feature = "popcnt", target_feature = "sse", target_feature = "sse2", target_feature = "sse3", target_feature = "sse4.1", target_feature = "sse4.2", target_feature = "ssse3"))
+            ("cmpxchg16b" && "fxsr" && "popcnt" && "sse" && "sse2" && "sse3" && "sse4.1" && "sse4.2" && "ssse3")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "x86-64-v3" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "avx", target_feature = "avx2", target_feature = "bmi1", target_feature = "bmi2", target_feature = "cmpxchg16b", target_feature = "f16c", target_feature = "fma", target_feature = "fxsr", target_feature = "lahfsahf", target_feature = "lzcnt", target_feature = "movbe", target_feature = "popcnt", target_feature = "sse", target_feature = "sse2", target_feature = "sse3", target_feature = "sse4.1", target_feature = "sse4.2", target_feature = "ssse3", target_feature = "xsave"))
+            ("avx" && "avx2" && "bmi1" && "bmi2" && "cmpxchg16b" && "f16c" && "fma" && "fxsr" && "lzcnt" && "movbe" && "popcnt" && "sse" && "sse2" && "sse3" && "sse4.1" && "sse4.2" && "ssse3" && "xsave")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "x86-64-v4" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "avx", target_feature = "avx2", target_feature = "avx512bw", target_feature = "avx512cd", target_feature = "avx512dq", target_feature = "avx512f", target_feature = "avx512vl", target_feature = "bmi1", target_feature = "bmi2", target_feature = "cmpxchg16b", target_feature = "f16c", target_feature = "fma", target_feature = "fxsr", target_feature = "lahfsahf", target_feature = "lzcnt", target_feature = "movbe", target_feature = "popcnt", target_feature = "sse", target_feature = "sse2", target_feature = "sse3", target_feature = "sse4.1", target_feature = "sse4.2", target_feature = "ssse3", target_feature = "xsave"))
+            ("avx" && "avx2" && "avx512bw" && "avx512cd" && "avx512dq" && "avx512f" && "avx512vl" && "bmi1" && "bmi2" && "cmpxchg16b" && "f16c" && "fma" && "fxsr" && "lzcnt" && "movbe" && "popcnt" && "sse" && "sse2" && "sse3" && "sse4.1" && "sse4.2" && "ssse3" && "xsave")
+            $($rest)*
+        )
+    };
+    // Arm64 (architecture versions)
+    (@__tgtfeat_dispatch_level "armv8-a" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "neon"))
+            ("neon")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "armv8.1-a" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "crc", target_feature = "lor", target_feature = "lse", target_feature = "neon", target_feature = "pan", target_feature = "rdm", target_feature = "vh"))
+            ("crc" && "lse" && "neon" && "rdm")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "armv8.2-a" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "crc", target_feature = "dpb", target_feature = "lor", target_feature = "lse", target_feature = "neon", target_feature = "pan", target_feature = "ras", target_feature = "rdm", target_feature = "vh"))
+            ("crc" && "dpb" && "lse" && "neon" && "rdm")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "armv8.3-a" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "crc", target_feature = "dpb", target_feature = "fcma", target_feature = "jsconv", target_feature = "lor", target_feature = "lse", target_feature = "neon", target_feature = "paca", target_feature = "pacg", target_feature = "pan", target_feature = "ras", target_feature = "rcpc", target_feature = "rdm", target_feature = "vh"))
+            ("crc" && "dpb" && "fcma" && "jsconv" && "lse" && "neon" && "paca" && "pacg" && "rcpc" && "rdm")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "armv8.4-a" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "crc", target_feature = "dit", target_feature = "dotprod", target_feature = "dpb", target_feature = "fcma", target_feature = "flagm", target_feature = "jsconv", target_feature = "lor", target_feature = "lse", target_feature = "lse2", target_feature = "neon", target_feature = "paca", target_feature = "pacg", target_feature = "pan", target_feature = "ras", target_feature = "rcpc", target_feature = "rcpc2", target_feature = "rdm", target_feature = "vh"))
+            ("crc" && "dit" && "dotprod" && "dpb" && "fcma" && "flagm" && "jsconv" && "lse" && "lse2" && "neon" && "paca" && "pacg" && "rcpc" && "rcpc2" && "rdm")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "armv8.5-a" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "bti", target_feature = "crc", target_feature = "dit", target_feature = "dotprod", target_feature = "dpb", target_feature = "dpb2", target_feature = "fcma", target_feature = "flagm", target_feature = "flagm2", target_feature = "frintts", target_feature = "jsconv", target_feature = "lor", target_feature = "lse", target_feature = "lse2", target_feature = "neon", target_feature = "paca", target_feature = "pacg", target_feature = "pan", target_feature = "ras", target_feature = "rcpc", target_feature = "rcpc2", target_feature = "rdm", target_feature = "sb", target_feature = "ssbs", target_feature = "vh"))
+            ("bti" && "crc" && "dit" && "dotprod" && "dpb" && "dpb2" && "fcma" && "flagm" && "frintts" && "jsconv" && "lse" && "lse2" && "neon" && "paca" && "pacg" && "rcpc" && "rcpc2" && "rdm" && "sb" && "ssbs")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "armv8.6-a" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "bf16", target_feature = "bti", target_feature = "crc", target_feature = "dit", target_feature = "dotprod", target_feature = "dpb", target_feature = "dpb2", target_feature = "ecv", target_feature = "fcma", target_feature = "flagm", target_feature = "flagm2", target_feature = "frintts", target_feature = "i8mm", target_feature = "jsconv", target_feature = "lor", target_feature = "lse", target_feature = "lse2", target_feature = "neon", target_feature = "paca", target_feature = "pacg", target_feature = "pan", target_feature = "ras", target_feature = "rcpc", target_feature = "rcpc2", target_feature = "rdm", target_feature = "sb", target_feature = "ssbs", target_feature = "vh"))
+            ("bf16" && "bti" && "crc" && "dit" && "dotprod" && "dpb" && "dpb2" && "fcma" && "flagm" && "frintts" && "i8mm" && "jsconv" && "lse" && "lse2" && "neon" && "paca" && "pacg" && "rcpc" && "rcpc2" && "rdm" && "sb" && "ssbs")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "armv9-a" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "bti", target_feature = "crc", target_feature = "dit", target_feature = "dotprod", target_feature = "dpb", target_feature = "dpb2", target_feature = "fcma", target_feature = "flagm", target_feature = "flagm2", target_feature = "frintts", target_feature = "jsconv", target_feature = "lor", target_feature = "lse", target_feature = "lse2", target_feature = "neon", target_feature = "paca", target_feature = "pacg", target_feature = "pan", target_feature = "ras", target_feature = "rcpc", target_feature = "rcpc2", target_feature = "rdm", target_feature = "sb", target_feature = "ssbs", target_feature = "sve", target_feature = "sve2", target_feature = "vh"))
+            ("bti" && "crc" && "dit" && "dotprod" && "dpb" && "dpb2" && "fcma" && "flagm" && "frintts" && "jsconv" && "lse" && "lse2" && "neon" && "paca" && "pacg" && "rcpc" && "rcpc2" && "rdm" && "sb" && "ssbs" && "sve" && "sve2")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_level "armv9.1-a" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "bf16", target_feature = "bti", target_feature = "crc", target_feature = "dit", target_feature = "dotprod", target_feature = "dpb", target_feature = "dpb2", target_feature = "ecv", target_feature = "fcma", target_feature = "flagm", target_feature = "flagm2", target_feature = "frintts", target_feature = "i8mm", target_feature = "jsconv", target_feature = "lor", target_feature = "lse", target_feature = "lse2", target_feature = "neon", target_feature = "paca", target_feature = "pacg", target_feature = "pan", target_feature = "ras", target_feature = "rcpc", target_feature = "rcpc2", target_feature = "rdm", target_feature = "sb", target_feature = "ssbs", target_feature = "sve", target_feature = "sve2", target_feature = "vh"))
+            ("bf16" && "bti" && "crc" && "dit" && "dotprod" && "dpb" && "dpb2" && "fcma" && "flagm" && "frintts" && "i8mm" && "jsconv" && "lse" && "lse2" && "neon" && "paca" && "pacg" && "rcpc" && "rcpc2" && "rdm" && "sb" && "ssbs" && "sve" && "sve2")
+            $($rest)*
+        )
+    };
+    // Unknown level.
+    (@__tgtfeat_dispatch_level $level: tt [$($callback: tt)*] $($rest: tt)*) => {
+        compile_error!(concat!("unknown level: ", stringify!($level)));
+    };
 
-        If the `else` clause exists, it is always used as the final fallback
-        (for both static and dynamic dispatching methods).
+    /*
+        RISC-V profiles.
 
-        If one of the following is specified:
+        Each profile is passed to the callback as a predicate (for static
+        dispatching) and a parenthesized condition (for dynamic dispatching).
+        Mandatory extensions which cannot be detected at runtime
+        (and the base ISA) are checked statically by `{static(...)}`.
+    */
+    (@__tgtfeat_dispatch_profile "rva20u64" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_arch = "riscv64", target_feature = "a", target_feature = "c", target_feature = "d", target_feature = "f", target_feature = "m", target_feature = "za128rs", target_feature = "ziccamoa", target_feature = "ziccif", target_feature = "zicclsm", target_feature = "ziccrse", target_feature = "zicntr", target_feature = "zicsr"))
+            ({static(all(target_arch = "riscv64", target_feature = "za128rs", target_feature = "ziccamoa", target_feature = "ziccif", target_feature = "zicclsm", target_feature = "ziccrse"))} && "a" && "c" && "d" && "f" && "m" && "zicntr" && "zicsr")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_profile "rva22u64" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_arch = "riscv64", target_feature = "a", target_feature = "c", target_feature = "d", target_feature = "f", target_feature = "m", target_feature = "za64rs", target_feature = "zba", target_feature = "zbb", target_feature = "zbs", target_feature = "zfhmin", target_feature = "zic64b", target_feature = "zicbom", target_feature = "zicbop", target_feature = "zicboz", target_feature = "ziccamoa", target_feature = "ziccif", target_feature = "zicclsm", target_feature = "ziccrse", target_feature = "zicntr", target_feature = "zicsr", target_feature = "zihintpause", target_feature = "zihpm", target_feature = "zkt"))
+            ({static(all(target_arch = "riscv64", target_feature = "za64rs", target_feature = "zic64b", target_feature = "zicbop", target_feature = "ziccamoa", target_feature = "ziccif", target_feature = "zicclsm", target_feature = "ziccrse"))} && "a" && "c" && "d" && "f" && "m" && "zba" && "zbb" && "zbs" && "zfhmin" && "zicbom" && "zicboz" && "zicntr" && "zicsr" && "zihintpause" && "zihpm" && "zkt")
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_profile "rva23u64" [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_arch = "riscv64", target_feature = "a", target_feature = "c", target_feature = "d", target_feature = "f", target_feature = "m", target_feature = "supm", target_feature = "v", target_feature = "za64rs", target_feature = "zawrs", target_feature = "zba", target_feature = "zbb", target_feature = "zbs", target_feature = "zcb", target_feature = "zcmop", target_feature = "zfa", target_feature = "zfhmin", target_feature = "zic64b", target_feature = "zicbom", target_feature = "zicbop", target_feature = "zicboz", target_feature = "ziccamoa", target_feature = "ziccif", target_feature = "zicclsm", target_feature = "ziccrse", target_feature = "zicntr", target_feature = "zicond", target_feature = "zicsr", target_feature = "zihintntl", target_feature = "zihintpause", target_feature = "zihpm", target_feature = "zimop", target_feature = "zkt", target_feature = "zvbb", target_feature = "zvfhmin", target_feature = "zvkt"))
+            ({static(all(target_arch = "riscv64", target_feature = "supm", target_feature = "za64rs", target_feature = "zic64b", target_feature = "zicbop", target_feature = "ziccamoa", target_feature = "ziccif", target_feature = "zicclsm", target_feature = "ziccrse"))} && "a" && "c" && "d" && "f" && "m" && "v" && "zawrs" && "zba" && "zbb" && "zbs" && "zcb" && "zcmop" && "zfa" && "zfhmin" && "zicbom" && "zicboz" && "zicntr" && "zicond" && "zicsr" && "zihintntl" && "zihintpause" && "zihpm" && "zimop" && "zkt" && "zvbb" && "zvfhmin" && "zvkt")
+            $($rest)*
+        )
+    };
+    // Unknown profile.
+    (@__tgtfeat_dispatch_profile $profile: tt [$($callback: tt)*] $($rest: tt)*) => {
+        compile_error!(concat!("unknown profile: ", stringify!($profile)));
+    };
+
+    /*
+        Runtime vector lengths (in bits).
+
+        Each condition is passed to the callback as a predicate (for static
+        dispatching) and a parenthesized condition (for dynamic dispatching).
+        On RISC-V, `vlen(>= N)` corresponds to the `zvlNb` feature and
+        `vlenb` is read only after "v" is detected (unless `zvlNb` is enabled
+        on compilation).  On AArch64, no target features correspond to
+        `sve_vl(>= N)` (never satisfied on static dispatching) and
+        the vector length is read only after "sve" is detected.
+        Those orders are required by the safety contract of the `unsafe`
+        functions reading vector lengths.
+    */
+    (@__tgtfeat_dispatch_vlen vlen 32 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl32b" 32 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 64 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl64b" 64 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 128 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl128b" 128 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 256 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl256b" 256 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 512 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl512b" 512 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 1024 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl1024b" 1024 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 2048 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl2048b" 2048 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 4096 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl4096b" 4096 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 8192 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl8192b" 8192 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 16384 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl16384b" 16384 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 32768 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl32768b" 32768 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen 65536 $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_vlen_zvl "zvl65536b" 65536 $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen vlen $bits: tt $($rest: tt)*) => {
+        compile_error!(concat!("unsupported vector length (a power of two from 32 to 65536 is expected): ", stringify!($bits)));
+    };
+    (@__tgtfeat_dispatch_vlen_zvl $zvl: tt $bits: tt [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            $($callback)*
+            (all(target_feature = "v", target_feature = $zvl))
+            ("v" && ({static(target_feature = $zvl)} || {vlen($bits)}))
+            $($rest)*
+        )
+    };
+    (@__tgtfeat_dispatch_vlen sve_vl $bits: literal [$($callback: tt)*] $($rest: tt)*) => {
+        $crate::target_feature_dispatch!($($callback)* (any()) ("sve" && {sve_vl($bits)}) $($rest)*)
+    };
+    (@__tgtfeat_dispatch_vlen sve_vl $bits: tt $($rest: tt)*) => {
+        compile_error!(concat!("unsupported vector length: ", stringify!($bits)));
+    };
 
-        1.  ( EXPR )
-        2.  { /* statements (results in an expression) */ }
-        3.  (empty)
+    /*
+        Feature condition (dynamic dispatching).
 
-        This is an architecture-only dispatch.
+        Since the condition is a valid Rust boolean expression once each
+        feature is replaced with the feature detection, it only replaces
+        features (keeping the operators and parentheses).
+        Validity of the condition is checked on the static dispatching path.
     */
-    // `if`-`else` chain without `else`.
-    (
-        @__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: meta),*) ($($else1: tt)*)
-        ($(if $($feat: tt)&&+ { $($if: tt)* })else+)
-    ) => {
+    // Operators.
+    (@__tgtfeat_dispatch_cond_dyn $detect: tt ($($expr: tt)*) && $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_dyn $detect ($($expr)* &&) $($rest)*)
+    };
+    (@__tgtfeat_dispatch_cond_dyn $detect: tt ($($expr: tt)*) || $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_dyn $detect ($($expr)* ||) $($rest)*)
+    };
+    (@__tgtfeat_dispatch_cond_dyn $detect: tt ($($expr: tt)*) ! $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_dyn $detect ($($expr)* !) $($rest)*)
+    };
+    // Parenthesized expression.
+    (@__tgtfeat_dispatch_cond_dyn $detect: tt ($($expr: tt)*) ($($inner: tt)+) $($rest: tt)*) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_feat_chain_entry
-            ($($arch$(($arch_arg))?)||+) ($($opts),*)
-            ($($else1)*) (@__tgtfeat_dispatch_no_fallback)
-            $((($($feat)&&+) ($($if)*)))+
+            @__tgtfeat_dispatch_cond_dyn $detect
+            ($($expr)* ($crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_dyn $detect () $($inner)+)))
+            $($rest)*
         )
     };
-    // `if`-`else` chain with invalid `else`.
-    (
-        @__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: meta),*) ($($else1: tt)*)
-        ($(if $($feat: tt)&&+ { $($if: tt)* })else+ else { @__tgtfeat_dispatch_no_fallback })
-    ) => {
-        compile_error!("invalid feature-specific `else` clause");
+    // Statically checked condition.
+    (@__tgtfeat_dispatch_cond_dyn $detect: tt ($($expr: tt)*) {static($($pred: tt)*)} $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_dyn $detect ($($expr)* {cfg!($($pred)*)}) $($rest)*)
     };
-    // `if`-`else` chain with `else`.
-    (
-        @__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: meta),*) ($($else1: tt)*)
-        ($(if $($feat: tt)&&+ { $($if: tt)* })else+ else { $($else2: tt)* })
-    ) => {
+    // Unchecked feature (detected as usual).
+    (@__tgtfeat_dispatch_cond_dyn $detect: tt ($($expr: tt)*) {unchecked($feat: literal)} $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_dyn $detect ($($expr)*) $feat $($rest)*)
+    };
+    // Runtime vector length.
+    (@__tgtfeat_dispatch_cond_dyn $detect: tt ($($expr: tt)*) {$kind: ident($bits: literal)} $($rest: tt)*) => {
         $crate::target_feature_dispatch!(
-            @__tgtfeat_dispatch_feat_chain_entry
-            ($($arch$(($arch_arg))?)||+) ($($opts),*)
-            ($($else2)*) ($($else2)*)
-            $((($($feat)&&+) ($($if)*)))+
+            @__tgtfeat_dispatch_cond_dyn $detect
+            ($($expr)* {$crate::__tgtfeat_dispatch_vector_length!($kind) >= $bits})
+            $($rest)*
         )
     };
-    // Architecture-only dispatch: Single expression enclosed by parens.
-    (@__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: meta),*) ($($else1: tt)*) (($expr: expr))) => {
-        $expr
+    // Feature.
+    (@__tgtfeat_dispatch_cond_dyn $detect: tt ($($expr: tt)*) $feat: tt $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_cond_dyn $detect
+            ($($expr)* {
+                $crate::__tgtfeat_dispatch_mask!(
+                    enabled $crate::target_feature_dispatch!(@__tgtfeat_dispatch_detect $detect $feat),
+                    $feat
+                )
+            })
+            $($rest)*
+        )
     };
-    // Architecture-only dispatch: Single block results in an expression.
-    (@__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: meta),*) ($($else1: tt)*) ({$($tt: tt)*})) => {
-        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($tt)*)
+    // End of the expression.
+    (@__tgtfeat_dispatch_cond_dyn $detect: tt ($($expr: tt)*)) => {
+        $($expr)*
     };
-    // Architecture-only dispatch: Empty (handle as returning the unit value).
-    (@__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: meta),*) ($($else1: tt)*) ()) => {
-        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr ())
+
+    /*
+        Feature detection (either a detection macro or a function
+        returning `bool`; prefixed by `fn`).
+
+        The result of a user-defined detector (prefixed by `@` and
+        the user's `unsafe` token) passes through the user's `unsafe` block
+        (which marks the detection result as trusted by the user).
+    */
+    (@__tgtfeat_dispatch_detect (@ $unsafe: tt fn $($detect: tt)+) $feat: tt) => {
+        {
+            let detected: bool = $($detect)+($feat);
+            #[allow(unused_unsafe)]
+            let detected = $unsafe { detected };
+            detected
+        }
     };
-    // Invalid architecture clause.
-    (@__tgtfeat_dispatch_arch_clause ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: meta),*) ($($else1: tt)*) ($($tt: tt)*)) => {
-        compile_error!("unsupported or invalid architecture clause");
+    (@__tgtfeat_dispatch_detect (@ $unsafe: tt $($detect: tt)+) $feat: tt) => {
+        {
+            let detected: bool = $($detect)+!($feat);
+            #[allow(unused_unsafe)]
+            let detected = $unsafe { detected };
+            detected
+        }
+    };
+    (@__tgtfeat_dispatch_detect ($($detect: tt)+) $feat: tt) => {
+        $($detect)+!($feat)
+    };
+    // Detection on filling a per-site cache (bracketed; applies the ceiling).
+    (@__tgtfeat_dispatch_detect [$detect: tt] $feat: tt) => {
+        $crate::__tgtfeat_dispatch_ceiling!(
+            $crate::target_feature_dispatch!(@__tgtfeat_dispatch_detect $detect $feat),
+            $feat
+        )
     };
 
     /*
@@ -660,6 +2640,45 @@ macro_rules! target_feature_dispatch {
             (::std::arch::is_s390x_feature_detected) $($rest)+
         )
     };
+    // Arm (32-bit and 64-bit; choose the detection macro per architecture)
+    (@__tgtfeat_dispatch_feat_chain_entry (family("arm")) ($($opts: meta),*) $($rest: tt)+) => {
+        {
+            #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec"))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_entry ("aarch64") ($($opts),*) $($rest)+)
+            }
+            #[cfg(target_arch = "arm")]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_entry ("arm") ($($opts),*) $($rest)+)
+            }
+        }
+    };
+    // MIPS (32-bit and 64-bit; choose the detection macro per architecture)
+    (@__tgtfeat_dispatch_feat_chain_entry (family("mips")) ($($opts: meta),*) $($rest: tt)+) => {
+        {
+            #[cfg(target_arch = "mips")]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_entry ("mips") ($($opts),*) $($rest)+)
+            }
+            #[cfg(target_arch = "mips64")]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_entry ("mips64") ($($opts),*) $($rest)+)
+            }
+        }
+    };
+    // PowerPC (32-bit and 64-bit; choose the detection macro per architecture)
+    (@__tgtfeat_dispatch_feat_chain_entry (family("powerpc")) ($($opts: meta),*) $($rest: tt)+) => {
+        {
+            #[cfg(target_arch = "powerpc")]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_entry ("powerpc") ($($opts),*) $($rest)+)
+            }
+            #[cfg(target_arch = "powerpc64")]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_entry ("powerpc64") ($($opts),*) $($rest)+)
+            }
+        }
+    };
     // Arm (32-bit and 64-bit)
     (@__tgtfeat_dispatch_feat_chain_entry (class("arm")) ($($opts: meta),*) $($rest: tt)+) => {
         $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_dispatch_static ($($opts),*) $($rest)+)
@@ -688,96 +2707,454 @@ macro_rules! target_feature_dispatch {
     (@__tgtfeat_dispatch_feat_chain_entry (class("wasm")) ($($opts: meta),*) $($rest: tt)+) => {
         $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_dispatch_static ($($opts),*) $($rest)+)
     };
-    // Others (use static dispatching only)
+    // User-defined family (choose the detection macro per architecture)
+    (@__tgtfeat_dispatch_feat_chain_entry (family([$name: ident: $($member: tt),+])) ($($opts: meta),*) $($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_members [$($member),+] ($($opts),*) ($($rest)+))
+    };
+    (@__tgtfeat_dispatch_feat_chain_members [$member: tt] ($($opts: meta),*) ($($rest: tt)+)) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_entry ($member) ($($opts),*) $($rest)+)
+    };
+    (@__tgtfeat_dispatch_feat_chain_members [$member: tt, $($member2: tt),+] ($($opts: meta),*) ($($rest: tt)+)) => {
+        {
+            #[cfg(target_arch = $member)]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_entry ($member) ($($opts),*) $($rest)+)
+            }
+            #[cfg(not(target_arch = $member))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_members [$($member2),+] ($($opts),*) ($($rest)+))
+            }
+        }
+    };
+    // Others (use static dispatching only; including user-defined classes)
     (@__tgtfeat_dispatch_feat_chain_entry ($($arch: tt $(($arch_arg: tt))?)||+) ($($opts: meta),*) $($rest: tt)+) => {
         $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_dispatch_static ($($opts),*) $($rest)+)
     };
 
+    /*
+        Verification of statically enabled features (x86 only; with the
+        `verify-static` feature and on debug builds).
+
+        Every feature in the conditions which is enabled on compilation
+        is checked against the running CPU.
+    */
+    (@__tgtfeat_dispatch_verify_static $(($($cond: tt)*))+) => {
+        #[cfg(debug_assertions)]
+        {
+            $($crate::target_feature_dispatch!(@__tgtfeat_dispatch_verify_cond $($cond)*);)*
+        }
+    };
+    (@__tgtfeat_dispatch_verify_cond {static($($pred: tt)*)} $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_verify_cond $($rest)*)
+    };
+    (@__tgtfeat_dispatch_verify_cond ($($inner: tt)*) $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_verify_cond $($inner)*);
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_verify_cond $($rest)*)
+    };
+    (@__tgtfeat_dispatch_verify_cond $feat: literal $($rest: tt)*) => {
+        $crate::__private::verify::check(cfg!(target_feature = $feat), $feat);
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_verify_cond $($rest)*)
+    };
+    (@__tgtfeat_dispatch_verify_cond $op: tt $($rest: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_verify_cond $($rest)*)
+    };
+    (@__tgtfeat_dispatch_verify_cond) => {};
+
     /*
         Feature-specific `if`-`else` chain (with specific dispatching).
     */
     // Dynamic dispatching (if enabled).
+    // Overridden detectors are applied first (the first matching one is used).
+    (
+        @__tgtfeat_dispatch_feat_chain_dispatch_dyn ($($opts: meta),*) $detect: tt
+        [(($cfg: meta) $detect2: tt) $($det: tt)*] $($rest: tt)+
+    ) => {
+        {
+            #[cfg($cfg)]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_dispatch_dyn ($($opts),*) $detect2 [] $($rest)+)
+            }
+            #[cfg(not($cfg))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_dispatch_dyn ($($opts),*) $detect [$($det)*] $($rest)+)
+            }
+        }
+    };
+    (
+        @__tgtfeat_dispatch_feat_chain_dispatch_dyn ($dyn: meta, $nightly: meta, $cached: meta, $all: meta) $detect: tt
+        [] ($($else_sta: tt)*) ($($else_dyn: tt)*) $($rest: tt)+
+    ) => {
+        {
+            #[cfg(all($dyn, not($cached), not($all)))]
+            {
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_feat_chain_dynamic
+                    $detect
+                    ($($else_sta)*) ($($else_dyn)*) $($rest)+
+                )
+            }
+            #[cfg(all($dyn, $cached, not($all)))]
+            {
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_feat_chain_cached
+                    $detect
+                    ($($else_sta)*) ($($else_dyn)*) $($rest)+
+                )
+            }
+            #[cfg(all($dyn, $all))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_all (dynamic $detect) ($($else_sta)*) $($rest)+)
+            }
+            #[cfg(all(not($dyn), not($all)))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_static ($($else_sta)*) $($rest)+)
+            }
+            #[cfg(all(not($dyn), $all))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_all (static) ($($else_sta)*) $($rest)+)
+            }
+        }
+    };
+    // Dynamic dispatching only on Nightly (and if enabled).
+    // With an overridden detector, Nightly features are not required.
+    (
+        @__tgtfeat_dispatch_feat_chain_dispatch_dyn_nightly ($($opts: meta),*) $detect: tt
+        [(($cfg: meta) $detect2: tt) $($det: tt)*] $($rest: tt)+
+    ) => {
+        {
+            #[cfg($cfg)]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_dispatch_dyn ($($opts),*) $detect2 [] $($rest)+)
+            }
+            #[cfg(not($cfg))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_dispatch_dyn_nightly ($($opts),*) $detect [$($det)*] $($rest)+)
+            }
+        }
+    };
+    (
+        @__tgtfeat_dispatch_feat_chain_dispatch_dyn_nightly ($dyn: meta, $nightly: meta, $cached: meta, $all: meta) $detect: tt
+        [] ($($else_sta: tt)*) ($($else_dyn: tt)*) $($rest: tt)+
+    ) => {
+        {
+            #[cfg(all($dyn, $nightly, not($cached), not($all)))]
+            {
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_feat_chain_dynamic
+                    $detect
+                    ($($else_sta)*) ($($else_dyn)*) $($rest)+
+                )
+            }
+            #[cfg(all($dyn, $nightly, $cached, not($all)))]
+            {
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_feat_chain_cached
+                    $detect
+                    ($($else_sta)*) ($($else_dyn)*) $($rest)+
+                )
+            }
+            #[cfg(all($dyn, $nightly, $all))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_all (dynamic $detect) ($($else_sta)*) $($rest)+)
+            }
+            #[cfg(all(not(all($dyn, $nightly)), not($all)))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_static ($($else_sta)*) $($rest)+)
+            }
+            #[cfg(all(not(all($dyn, $nightly)), $all))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_all (static) ($($else_sta)*) $($rest)+)
+            }
+        }
+    };
+    // Static (only) dispatching (detectors are ignored).
+    (
+        @__tgtfeat_dispatch_feat_chain_dispatch_static ($dyn: meta, $nightly: meta, $cached: meta, $all: meta)
+        [$($det: tt)*] ($($else_sta: tt)*) ($($else_dyn: tt)*) $($rest: tt)+
+    ) => {
+        {
+            #[cfg(not($all))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_static ($($else_sta)*) $($rest)+)
+            }
+            #[cfg($all)]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_all (static) ($($else_sta)*) $($rest)+)
+            }
+        }
+    };
+
+    /*
+        Feature-specific dynamic dispatching.
+    */
+    // No feature-specific fallback
+    // (use static dispatching with global fallback).
+    (
+        @__tgtfeat_dispatch_feat_chain_dynamic $detect: tt
+        ($($else_sta: tt)*) (@__tgtfeat_dispatch_no_fallback) $((($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)))+
+    ) => {
+        $(
+            if $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_dyn $detect () $($cond)*) {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($if)*)
+            }
+        )else+
+        else {
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_feat_chain_static
+                ($($else_sta)*) $((($($cfg)*) ($($cond)*) ($($if)*)))+
+            )
+        }
+    };
+    // Architecture-specific fallback is specified
+    // (always use local fallback).
+    (
+        @__tgtfeat_dispatch_feat_chain_dynamic $detect: tt
+        ($($else_sta: tt)*) ($($else_dyn: tt)*) $((($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)))+
+    ) => {
+        $(
+            if $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_dyn $detect () $($cond)*) {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($if)*)
+            }
+        )else+
+        else {
+            $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($else_dyn)*)
+        }
+    };
+
+    /*
+        Feature-specific dynamic dispatching (cached).
+
+        The index of the selected clause is stored in a per-site atomic
+        variable on the first run:
+
+        *   0:        Not yet detected
+        *   1..=N:    N-th feature-specific clause
+        *   N+1:      Fallback (the result of the static dispatching or
+                      the feature-specific `else` clause)
+
+        Only the first run performs feature detection.
+        Succeeding runs only compare the stored index.
+    */
+    // No feature-specific fallback
+    // (use static dispatching with global fallback).
+    (
+        @__tgtfeat_dispatch_feat_chain_cached $detect: tt
+        ($($else_sta: tt)*) (@__tgtfeat_dispatch_no_fallback) $((($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)))+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_chain_cached_2 $detect
+            (
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_feat_chain_static
+                    ($($else_sta)*) $((($($cfg)*) ($($cond)*) ($($if)*)))+
+                )
+            )
+            $((($($cfg)*) ($($cond)*) ($($if)*)))+
+        )
+    };
+    // Architecture-specific fallback is specified
+    // (always use local fallback).
+    (
+        @__tgtfeat_dispatch_feat_chain_cached $detect: tt
+        ($($else_sta: tt)*) ($($else_dyn: tt)*) $((($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)))+
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_feat_chain_cached_2 $detect
+            ($crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($else_dyn)*))
+            $((($($cfg)*) ($($cond)*) ($($if)*)))+
+        )
+    };
+    // Per-site cache and dispatching by the cached index.
+    (
+        @__tgtfeat_dispatch_feat_chain_cached_2 $detect: tt
+        ($($fallback: tt)*) $((($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)))+
+    ) => {
+        {
+            static __TGTFEAT_DISPATCH_CACHE: ::core::sync::atomic::AtomicU8 =
+                ::core::sync::atomic::AtomicU8::new(0);
+            let mut __tgtfeat_dispatch_index =
+                __TGTFEAT_DISPATCH_CACHE.load(::core::sync::atomic::Ordering::Relaxed);
+            $crate::__tgtfeat_dispatch_mask!(
+                cached (__TGTFEAT_DISPATCH_CACHE, __tgtfeat_dispatch_index)
+                $crate::target_feature_dispatch!(
+                    @__tgtfeat_dispatch_feat_chain_cached_detect [$detect] (1u8)
+                    $(($($cond)*))+
+                )
+            );
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_feat_chain_cached_select
+                (__tgtfeat_dispatch_index) (1u8) ($($fallback)*)
+                $((($($cfg)*) ($($cond)*) ($($if)*)))+
+            )
+        }
+    };
+    // Detection: `if`
+    (@__tgtfeat_dispatch_feat_chain_cached_detect $detect: tt ($($index: tt)+) ($($cond: tt)*) $($rest: tt)*) => {
+        if $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_dyn $detect () $($cond)*) {
+            $($index)+
+        } else {
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_feat_chain_cached_detect $detect ($($index)+ + 1u8)
+                $($rest)*
+            )
+        }
+    };
+    // Detection: `else`
+    (@__tgtfeat_dispatch_feat_chain_cached_detect $detect: tt ($($index: tt)+)) => {
+        $($index)+
+    };
+    // Selection: `if`
+    (
+        @__tgtfeat_dispatch_feat_chain_cached_select ($var: ident) ($($index: tt)+) ($($fallback: tt)*)
+        (($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)) $($rest: tt)*
+    ) => {
+        if $var == $($index)+ {
+            $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($if)*)
+        } else {
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_feat_chain_cached_select
+                ($var) ($($index)+ + 1u8) ($($fallback)*) $($rest)*
+            )
+        }
+    };
+    // Selection: `else`
+    (@__tgtfeat_dispatch_feat_chain_cached_select ($var: ident) ($($index: tt)+) ($($fallback: tt)*)) => {
+        $($fallback)*
+    };
+
+    /*
+        Evaluation of all branches (if the `all_branches` option is enabled).
+
+        Every feature-specific clause whose condition is satisfied (detected
+        on dynamic dispatching or enabled on static dispatching) is evaluated
+        in order, followed by the fallback.  The result is an iterator of
+        (LABEL, RESULT) pairs (each body is already converted to the pair;
+        see @__tgtfeat_dispatch_clause_body).
+
+        The fallback is always evaluated.  It is the feature-specific `else`
+        clause (if any) or the fallback of the root.
+    */
+    (
+        @__tgtfeat_dispatch_feat_chain_all $method: tt ($($fallback: tt)*)
+        $((($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)))+
+    ) => {
+        ::core::iter::Iterator::flatten(::core::iter::IntoIterator::into_iter([
+            $(
+                if $crate::target_feature_dispatch!(@__tgtfeat_dispatch_all_cond $method ($($cfg)*) $($cond)*) {
+                    ::core::option::Option::Some($crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($if)*))
+                } else {
+                    ::core::option::Option::None
+                },
+            )+
+            ::core::option::Option::Some($crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($fallback)*)),
+        ]))
+    };
+    // Condition: dynamic dispatching.
+    (@__tgtfeat_dispatch_all_cond (dynamic $detect: tt) ($($cfg: tt)*) $($cond: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_cond_dyn $detect () $($cond)*)
+    };
+    // Condition: static dispatching.
+    (@__tgtfeat_dispatch_all_cond (static) ($($cfg: tt)*) $($cond: tt)*) => {
+        cfg!($($cfg)*)
+    };
+
+    /*
+        Body of a feature-specific clause, the fallback and the only result.
+
+        Depending on the options, the body is converted to:
+
+        *   LABEL:            `selected_label` (the body is not evaluated;
+                              combined with `all_branches`, the result is
+                              an iterator of labels)
+        *   (LABEL, RESULT):  `all_branches`
+        *   RESULT:           Otherwise
+
+        The label of a feature-specific clause is specified by `#[label]` or
+        derived from the condition.  The label of others is "fallback".
+    */
+    // Feature-specific clause with a label.
+    (
+        @__tgtfeat_dispatch_clause_body ($enable: meta, $all: meta, $query: meta) ($($cond: tt)*)
+        @__tgtfeat_dispatch_labeled ($label: literal) $($body: tt)*
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_clause_body_2 ($enable, $all, $query) ($($cond)*) ($label) $($body)*
+        )
+    };
+    // Feature-specific clause without a label.
+    (@__tgtfeat_dispatch_clause_body ($enable: meta, $all: meta, $query: meta) ($($cond: tt)*) $($body: tt)*) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_clause_body_2 ($enable, $all, $query) ($($cond)*)
+            ($crate::target_feature_dispatch!(@__tgtfeat_dispatch_default_label $($cond)*))
+            $($body)*
+        )
+    };
     (
-        @__tgtfeat_dispatch_feat_chain_dispatch_dyn ($dyn: meta, $nightly: meta) ($detect: path)
-        ($($else_sta: tt)*) ($($else_dyn: tt)*) $($rest: tt)+
+        @__tgtfeat_dispatch_clause_body_2 ($enable: meta, $all: meta, $query: meta) ($($cond: tt)*)
+        ($($label: tt)*) $($body: tt)*
     ) => {
         {
-            #[cfg($dyn)]
+            #[cfg($query)]
             {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_feat_chain_dynamic
-                    ($detect)
-                    ($($else_sta)*) ($($else_dyn)*) $($rest)+
+                $($label)*
+            }
+            #[cfg(all(not($query), $all))]
+            {
+                (
+                    $($label)*,
+                    $crate::target_feature_dispatch!(@__tgtfeat_dispatch_enable_body ($enable) ($($cond)*) $($body)*),
                 )
             }
-            #[cfg(not($dyn))]
+            #[cfg(not(any($query, $all)))]
             {
-                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_static ($($else_sta)*) $($rest)+)
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_enable_body ($enable) ($($cond)*) $($body)*)
             }
         }
     };
-    // Dynamic dispatching only on Nightly (and if enabled).
-    (
-        @__tgtfeat_dispatch_feat_chain_dispatch_dyn_nightly ($dyn: meta, $nightly: meta) ($detect: path)
-        ($($else_sta: tt)*) ($($else_dyn: tt)*) $($rest: tt)+
-    ) => {
+    // Default label: the feature itself (single feature).
+    (@__tgtfeat_dispatch_default_label $feat: literal) => {
+        $feat
+    };
+    // Default label: the condition as written.
+    (@__tgtfeat_dispatch_default_label $($cond: tt)*) => {
+        stringify!($($cond)*)
+    };
+    // Fallback of a feature-specific chain.
+    (@__tgtfeat_dispatch_fallback_body ($all: meta, $query: meta) $($body: tt)*) => {
         {
-            #[cfg(all($dyn, $nightly))]
+            #[cfg($query)]
             {
-                $crate::target_feature_dispatch!(
-                    @__tgtfeat_dispatch_feat_chain_dynamic
-                    ($detect)
-                    ($($else_sta)*) ($($else_dyn)*) $($rest)+
-                )
+                "fallback"
             }
-            #[cfg(not(all($dyn, $nightly)))]
+            #[cfg(all(not($query), $all))]
             {
-                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_static ($($else_sta)*) $($rest)+)
+                ("fallback", $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($body)*))
             }
-        }
-    };
-    // Static (only) dispatching.
-    (
-        @__tgtfeat_dispatch_feat_chain_dispatch_static ($dyn: meta, $nightly: meta)
-        ($($else_sta: tt)*) ($($else_dyn: tt)*) $($rest: tt)+
-    ) => {
-        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_feat_chain_static ($($else_sta)*) $($rest)+)
-    };
-
-    /*
-        Feature-specific dynamic dispatching.
-    */
-    // No feature-specific fallback
-    // (use static dispatching with global fallback).
-    (
-        @__tgtfeat_dispatch_feat_chain_dynamic ($detect: path)
-        ($($else_sta: tt)*) (@__tgtfeat_dispatch_no_fallback) $((($($feat: tt)&&+) ($($if: tt)*)))+
-    ) => {
-        $(
-            if $({$detect!($feat)})&&+ {
-                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($if)*)
+            #[cfg(not(any($query, $all)))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($body)*)
             }
-        )else+
-        else {
-            $crate::target_feature_dispatch!(
-                @__tgtfeat_dispatch_feat_chain_static
-                ($($else_sta)*) $((($($feat)&&+) ($($if)*)))+
-            )
         }
     };
-    // Architecture-specific fallback is specified
-    // (always use local fallback).
-    (
-        @__tgtfeat_dispatch_feat_chain_dynamic ($detect: path)
-        ($($else_sta: tt)*) ($($else_dyn: tt)*) $((($($feat: tt)&&+) ($($if: tt)*)))+
-    ) => {
-        $(
-            if $({$detect!($feat)})&&+ {
-                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($if)*)
+    // The only result (without feature-specific clauses).
+    (@__tgtfeat_dispatch_single ($all: meta, $query: meta) $($body: tt)*) => {
+        {
+            #[cfg(all($query, not($all)))]
+            {
+                "fallback"
+            }
+            #[cfg(all($query, $all))]
+            {
+                ::core::iter::once("fallback")
+            }
+            #[cfg(all(not($query), $all))]
+            {
+                ::core::iter::once(("fallback", $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($body)*)))
+            }
+            #[cfg(not(any($query, $all)))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($body)*)
             }
-        )else+
-        else {
-            $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($else_dyn)*)
         }
     };
 
@@ -785,13 +3162,13 @@ macro_rules! target_feature_dispatch {
         Feature-specific static dispatching.
     */
     // `if`
-    (@__tgtfeat_dispatch_feat_chain_static ($($else: tt)*) (($($feat: tt)&&+) ($($if: tt)*)) $($rest: tt)*) => {
+    (@__tgtfeat_dispatch_feat_chain_static ($($else: tt)*) (($($cfg: tt)*) ($($cond: tt)*) ($($if: tt)*)) $($rest: tt)*) => {
         {
-            #[cfg(all($(target_feature = $feat),+))]
+            #[cfg($($cfg)*)]
             {
                 $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($if)*)
             }
-            #[cfg(not(all($(target_feature = $feat),+)))]
+            #[cfg(not($($cfg)*))]
             {
                 $crate::target_feature_dispatch!(
                     @__tgtfeat_dispatch_feat_chain_static
@@ -825,4 +3202,580 @@ macro_rules! target_feature_dispatch {
     (@__tgtfeat_dispatch_as_expr_2 $($tt: tt)*) => {
         compile_error!(concat!("failed to parse ", stringify!($($tt)*), " as expression"));
     };
+
+    /*
+        Body of a feature-specific clause with features enabled
+        (if the `enable_features` option is enabled).
+
+        If the condition is a conjunction of features (including
+        parenthesized ones such as named levels), the body is wrapped in
+        a closure called from an inner function with those features enabled
+        so that the body is compiled with them (after inlining).
+        Otherwise, the body is left as is.
+
+        Because the body is a closure, control flow escaping the body is
+        rejected: at the top level of the body by the check below and
+        inside nested expressions by the return type of the closure
+        (`Body`, which neither `return` expressions nor `?` can produce)
+        or the compiler (`break` and `continue` targeting an outer loop).
+        Nested closures and functions in the body are not affected.
+    */
+    (@__tgtfeat_dispatch_enable_body ($enable: meta) ($($cond: tt)*) $($body: tt)*) => {
+        {
+            #[cfg($enable)]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_enable_body_feats () ($($body)*) $($cond)*)
+            }
+            #[cfg(not($enable))]
+            {
+                $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($body)*)
+            }
+        }
+    };
+    // Collect features of the conjunction.
+    (
+        @__tgtfeat_dispatch_enable_body_feats ($($feats: literal)*) ($($body: tt)*)
+        $feat: literal $(&& $($rest: tt)+)?
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_enable_body_feats ($($feats)* $feat) ($($body)*) $($($rest)+)?
+        )
+    };
+    (
+        @__tgtfeat_dispatch_enable_body_feats ($($feats: literal)*) ($($body: tt)*)
+        ($($feat: literal)&&+) $(&& $($rest: tt)+)?
+    ) => {
+        $crate::target_feature_dispatch!(
+            @__tgtfeat_dispatch_enable_body_feats ($($feats)* $($feat)+) ($($body)*) $($($rest)+)?
+        )
+    };
+    // Conjunction of features (call the body with features enabled).
+    (@__tgtfeat_dispatch_enable_body_feats ($($feats: literal)+) ($($body: tt)*)) => {
+        {
+            $crate::target_feature_dispatch!(@__tgtfeat_dispatch_enable_check $($body)*);
+            $(#[target_feature(enable = $feats)])+
+            #[inline]
+            unsafe fn __tgtfeat_dispatch_enabled<R>(
+                f: impl FnOnce() -> $crate::__private::enable::Body<R>,
+            ) -> R {
+                f().0
+            }
+            // The closure is created outside of the `unsafe` block
+            // (so that the body is not an unsafe context).
+            let __tgtfeat_dispatch_body = || -> $crate::__private::enable::Body<_> {
+                $crate::__private::enable::Body(
+                    $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($body)*)
+                )
+            };
+            // SAFETY: The clause is selected only if all features are enabled or detected.
+            unsafe { __tgtfeat_dispatch_enabled(__tgtfeat_dispatch_body) }
+        }
+    };
+    // Other conditions (left as is).
+    (@__tgtfeat_dispatch_enable_body_feats ($($feats: literal)*) ($($body: tt)*) $($_cond: tt)*) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_as_expr $($body)*)
+    };
+    // Check the top level of the body for control flow escaping the closure
+    // (nested ones are rejected by the type of the closure or the compiler).
+    (@__tgtfeat_dispatch_enable_check $($tt: tt)*) => {
+        $($crate::target_feature_dispatch!(@__tgtfeat_dispatch_enable_check_token $tt);)*
+    };
+    (@__tgtfeat_dispatch_enable_check_token return) => {
+        compile_error!("`return` cannot be used in the body of a clause with `enable_features` (the body is a closure)");
+    };
+    (@__tgtfeat_dispatch_enable_check_token ?) => {
+        compile_error!("`?` cannot be used in the body of a clause with `enable_features` (the body is a closure)");
+    };
+    (@__tgtfeat_dispatch_enable_check_token break) => {
+        compile_error!("`break` cannot be used in the body of a clause with `enable_features` (the body is a closure)");
+    };
+    (@__tgtfeat_dispatch_enable_check_token continue) => {
+        compile_error!("`continue` cannot be used in the body of a clause with `enable_features` (the body is a closure)");
+    };
+    (@__tgtfeat_dispatch_enable_check_token $_tt: tt) => {};
+
+    /*
+        Proof token(s) of given features
+        (a tuple of tokens if multiple features are given).
+
+        It is only expanded in the body of the feature-specific clause
+        which is selected only if all features are enabled (statically)
+        or detected (dynamically).
+    */
+    (@__tgtfeat_dispatch_token $feat: literal) => {
+        // SAFETY: The feature is enabled or detected (see above).
+        unsafe { <$crate::tokens::FeatureToken<{ $crate::__private::tokens::index($feat) }>>::new() }
+    };
+    (@__tgtfeat_dispatch_token $($feat: literal)&&+) => {
+        ($($crate::target_feature_dispatch!(@__tgtfeat_dispatch_token $feat)),+)
+    };
+    (@__tgtfeat_dispatch_token $($feat: tt)*) => {
+        compile_error!(concat!(
+            "proof tokens are only available for conjunctions of features: ",
+            stringify!($($feat)*)
+        ))
+    };
+}
+
+#[doc = include_str!("../docs/target_feature_dispatch_fn.md")]
+#[macro_export]
+macro_rules! target_feature_dispatch_fn {
+    /*
+        Public Interface.
+
+        Each architecture clause contains a comma-separated list of
+        feature conjunctions (e.g. `"avx2", "sse4.1" && "popcnt"`).
+    */
+    ($(#[$($pseudo_meta: tt)+])* if $($rest: tt)+) => {
+        $crate::target_feature_dispatch_fn! {
+            @__tgtfeat_dispatch_fn_parse
+            ($(#[$($pseudo_meta)+])*) () if $($rest)+
+        }
+    };
+
+    /*
+        Parser of the architecture-specific `if`-`else` chain
+        (followed by the function item).
+    */
+    // Architecture clause.
+    (
+        @__tgtfeat_dispatch_fn_parse ($($opts: tt)*) ($($archs: tt)*)
+        if $($arch: tt $(($arch_arg: tt))?)||+ { $($feats: tt)* } $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch_fn! {
+            @__tgtfeat_dispatch_fn_parse ($($opts)*)
+            ($($archs)* (($($arch$(($arch_arg))?)||+) ($($feats)*)))
+            $($rest)*
+        }
+    };
+    (@__tgtfeat_dispatch_fn_parse ($($opts: tt)*) ($($archs: tt)+) else if $($rest: tt)+) => {
+        $crate::target_feature_dispatch_fn! { @__tgtfeat_dispatch_fn_parse ($($opts)*) ($($archs)+) if $($rest)+ }
+    };
+    // `else` (invalid).
+    (@__tgtfeat_dispatch_fn_parse ($($opts: tt)*) ($($archs: tt)+) else $($rest: tt)*) => {
+        compile_error!("`else` clause is not allowed (the function body is the fallback)");
+    };
+    // Function item.
+    (
+        @__tgtfeat_dispatch_fn_parse ($($opts: tt)*) ($($archs: tt)+)
+        $(#[$($attr: tt)+])*
+        $vis: vis fn $name: ident ($($arg: ident: $ty: ty),* $(,)?) $(-> $ret: ty)? $body: block
+    ) => {
+        $(#[$($attr)+])*
+        $vis fn $name($($arg: $ty),*) $(-> $ret)? {
+            $crate::target_feature_dispatch_fn!(
+                @__tgtfeat_dispatch_fn_arch
+                [($($arg: $ty),*) ($($arg),*) ($(-> $ret)?) $body]
+                ($($opts)*) () $($archs)+
+            )
+        }
+    };
+    // Invalid function item.
+    (@__tgtfeat_dispatch_fn_parse ($($opts: tt)*) ($($archs: tt)*) $($rest: tt)*) => {
+        compile_error!(concat!("unsupported or invalid function item: ", stringify!($($rest)*)));
+    };
+
+    /*
+        Architecture clauses.
+
+        [FN] is the function signature and the body in the following form:
+        [(PARAMS) (ARGS) (-> RET) BODY]
+    */
+    // Architecture clause (convert the feature list).
+    (
+        @__tgtfeat_dispatch_fn_arch [$($fn: tt)*] ($($opts: tt)*) ($($chain: tt)*)
+        (($($arch: tt)*) ($($feats: tt)*)) $($rest: tt)*
+    ) => {
+        $crate::target_feature_dispatch_fn!(
+            @__tgtfeat_dispatch_fn_feat [$($fn)*] ($($opts)*) ($($chain)*)
+            ($($arch)*) () ($($rest)*) $($feats)*
+        )
+    };
+    // End (pass to the dispatching macro with the original body as the fallback).
+    (@__tgtfeat_dispatch_fn_arch [($($params: tt)*) ($($args: tt)*) ($($ret: tt)*) $body: block] ($($opts: tt)*) (else $($chain: tt)*)) => {
+        $crate::target_feature_dispatch! {
+            $($opts)*
+            $($chain)*
+            else { $body }
+        }
+    };
+
+    /*
+        Feature clauses (each creates a function clone with given features).
+    */
+    // Feature conjunction.
+    (
+        @__tgtfeat_dispatch_fn_feat [$($fn: tt)*] ($($opts: tt)*) ($($chain: tt)*)
+        ($($arch: tt)*) ($($clauses: tt)*) ($($rest: tt)*) $($feat: tt)&&+ $(, $($feats: tt)*)?
+    ) => {
+        $crate::target_feature_dispatch_fn!(
+            @__tgtfeat_dispatch_fn_feat [$($fn)*] ($($opts)*) ($($chain)*)
+            ($($arch)*)
+            ($($clauses)* else if $($feat)&&+ {
+                $crate::target_feature_dispatch_fn!(@__tgtfeat_dispatch_fn_clone [$($fn)*] $($feat)&&+)
+            })
+            ($($rest)*) $($($feats)*)?
+        )
+    };
+    // End of the architecture clause.
+    (
+        @__tgtfeat_dispatch_fn_feat [$($fn: tt)*] ($($opts: tt)*) ($($chain: tt)*)
+        ($($arch: tt)*) (else $($clauses: tt)+) ($($rest: tt)*)
+    ) => {
+        $crate::target_feature_dispatch_fn!(
+            @__tgtfeat_dispatch_fn_arch [$($fn)*] ($($opts)*)
+            ($($chain)* else if $($arch)* { $($clauses)+ })
+            $($rest)*
+        )
+    };
+    // Empty or invalid feature list.
+    (
+        @__tgtfeat_dispatch_fn_feat [$($fn: tt)*] ($($opts: tt)*) ($($chain: tt)*)
+        ($($arch: tt)*) ($($clauses: tt)*) ($($rest: tt)*) $($feats: tt)*
+    ) => {
+        compile_error!(concat!(
+            "invalid feature list for ", stringify!($($arch)*), ": ",
+            stringify!($($feats)*)
+        ));
+    };
+
+    /*
+        Function clone with given features.
+
+        Calling this clone is safe because the dispatching macro
+        only selects it when all features are enabled
+        (statically) or detected (dynamically).
+
+        The user body is placed in a safe inner function (inlined into
+        the clone to inherit its features) so that the body is not
+        an unsafe context.
+    */
+    (@__tgtfeat_dispatch_fn_clone [($($params: tt)*) ($($args: tt)*) ($($ret: tt)*) $body: block] $($feat: literal)&&+) => {{
+        $(#[target_feature(enable = $feat)])+
+        unsafe fn __tgtfeat_dispatch_fn_clone($($params)*) $($ret)* {
+            #[inline(always)]
+            fn __tgtfeat_dispatch_fn_body($($params)*) $($ret)* $body
+            __tgtfeat_dispatch_fn_body($($args)*)
+        }
+        // SAFETY: The clone is only selected when all of its features are available.
+        unsafe { __tgtfeat_dispatch_fn_clone($($args)*) }
+    }};
+}
+
+#[doc = include_str!("../docs/dispatch_static.md")]
+#[macro_export]
+macro_rules! dispatch_static {
+    /*
+        Public Interface.
+
+        Each item is a function signature followed by `=` and
+        the input of `target_feature_dispatch` (evaluated to a function).
+    */
+    () => {};
+    (
+        $(#[$($attr: tt)+])*
+        $vis: vis fn $name: ident ($($arg: ident: $ty: ty),* $(,)?) $(-> $ret: ty)? =
+        $(#[$($pseudo_meta: tt)+])*
+        $(if $($arch: tt $(($arch_arg: tt))?)||+ { $($if: tt)* })else+ else { $($else: tt)* };
+        $($rest: tt)*
+    ) => {
+        $(#[$($attr)+])*
+        $vis fn $name($($arg: $ty),*) $(-> $ret)? {
+            $crate::target_feature_dispatch!(
+                @__tgtfeat_dispatch_options_then [$crate::dispatch_static]
+                (
+                    @__tgtfeat_dispatch_static_body
+                    [($($arg),*) ($($ty),*) ($(-> $ret)?)]
+                    ($(#[$($pseudo_meta)+])*)
+                    ($(if $($arch$(($arch_arg))?)||+ { $($if)* })else+ else { $($else)* })
+                )
+                $(#[$($pseudo_meta)+])*
+            )
+        }
+        $crate::dispatch_static! { $($rest)* }
+    };
+
+    /*
+        Function body.
+
+        With dynamic dispatching, the index of the selected clause is cached
+        in a static variable (just like the `cached` option) and the selected
+        function is called.  Otherwise, the function is called directly.
+    */
+    (
+        @__tgtfeat_dispatch_static_body
+        [($($args: tt)*) ($($types: tt)*) ($($ret: tt)*)] ($($attrs: tt)*) ($($chain: tt)*)
+        ($dyn: meta, $nightly: meta, $non_fallback: meta, $cached: meta, $enable: meta, $all: meta, $query: meta, $verify: meta, [$($det: tt)*])
+    ) => {{
+        #[cfg($all)]
+        ::core::compile_error!("`all_branches` is not supported by `dispatch_static!`");
+        #[cfg($query)]
+        ::core::compile_error!("`selected_label` is not supported by `dispatch_static!`");
+        #[cfg(all($non_fallback, $dyn))]
+        {
+            let func: fn($($types)*) $($ret)* = $crate::target_feature_dispatch! { $($attrs)* #[cached] $($chain)* };
+            func($($args)*)
+        }
+        #[cfg(not(all($non_fallback, $dyn)))]
+        {
+            ($crate::target_feature_dispatch! { $($attrs)* $($chain)* })($($args)*)
+        }
+    }};
+}
+
+#[doc = include_str!("../docs/define_arch_family.md")]
+#[macro_export]
+macro_rules! define_arch_family {
+    ($(#[$attr: meta])* pub $name: ident = $($arch: tt $(($arch_arg: tt))?)||+) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_define_arch_set ($) family ($(#[$attr])*) (pub) $name)
+            () ($($arch$(($arch_arg))?)||+)
+        }
+    };
+    ($(#[$attr: meta])* $vis: vis $name: ident = $($arch: tt $(($arch_arg: tt))?)||+) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_define_arch_set ($) family ($(#[$attr])*) ($vis) $name)
+            () ($($arch$(($arch_arg))?)||+)
+        }
+    };
+}
+
+#[doc = include_str!("../docs/define_arch_class.md")]
+#[macro_export]
+macro_rules! define_arch_class {
+    ($(#[$attr: meta])* pub $name: ident = $($arch: tt $(($arch_arg: tt))?)||+) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_define_arch_set ($) class ($(#[$attr])*) (pub) $name)
+            () ($($arch$(($arch_arg))?)||+)
+        }
+    };
+    ($(#[$attr: meta])* $vis: vis $name: ident = $($arch: tt $(($arch_arg: tt))?)||+) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_arch_list [$crate::target_feature_dispatch]
+            (@__tgtfeat_dispatch_define_arch_set ($) class ($(#[$attr])*) ($vis) $name)
+            () ($($arch$(($arch_arg))?)||+)
+        }
+    };
+}
+
+#[doc = include_str!("../docs/define_feature_set.md")]
+#[macro_export]
+macro_rules! define_feature_set {
+    ($(#[$attr: meta])* pub $name: ident = $($cond: tt)+) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_define_feature_set ($) ($(#[$attr])*) (pub) $name ($($cond)+)
+        }
+    };
+    ($(#[$attr: meta])* $vis: vis $name: ident = $($cond: tt)+) => {
+        $crate::target_feature_dispatch! {
+            @__tgtfeat_dispatch_define_feature_set ($) ($(#[$attr])*) ($vis) $name ($($cond)+)
+        }
+    };
+}
+
+/// Registers a named dispatching site (internal; see the `sites` module).
+#[cfg(all(feature = "sites", any(target_os = "linux", target_os = "android")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_register_site {
+    ($name: literal, $resolve: expr, $labels: expr) => {
+        #[allow(unsafe_code)]
+        #[used]
+        #[unsafe(link_section = "target_feature_dispatch_sites")]
+        static __TGTFEAT_DISPATCH_SITE: $crate::sites::Site =
+            $crate::sites::Site::__new($name, $resolve, $labels);
+    };
+}
+
+/// Registers a named dispatching site (internal; no-op on this configuration).
+#[cfg(not(all(feature = "sites", any(target_os = "linux", target_os = "android"))))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_register_site {
+    ($name: literal, $resolve: expr, $labels: expr) => {};
+}
+
+/// Verifies statically enabled features (internal; on x86).
+#[cfg(all(
+    feature = "verify-static",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_verify_static {
+    ($($rest: tt)+) => {
+        $crate::target_feature_dispatch!(@__tgtfeat_dispatch_verify_static $($rest)+)
+    };
+}
+
+/// Verifies statically enabled features (internal; unsupported architectures).
+#[cfg(all(
+    feature = "verify-static",
+    not(any(target_arch = "x86", target_arch = "x86_64"))
+))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_verify_static {
+    ($($rest: tt)+) => {
+        ::core::compile_error!("`verify_static` is only supported on x86 (32-bit and 64-bit)");
+    };
+}
+
+/// Verifies statically enabled features (internal; without the feature).
+#[cfg(not(feature = "verify-static"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_verify_static {
+    ($($rest: tt)+) => {
+        ::core::compile_error!("`verify_static` requires the `verify-static` feature");
+    };
+}
+
+/// Detects a feature by CPUID (internal; see the `cpuid` module).
+#[cfg(all(feature = "cpuid", any(target_arch = "x86", target_arch = "x86_64")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_detect_cpuid {
+    ($feat: tt) => {{
+        const _: () = $crate::__private::cpuid::check($feat);
+        (::core::cfg!(target_feature = $feat) || $crate::cpuid::is_detected($feat))
+    }};
+}
+
+/// Detects a feature by CPUID (internal; without the feature).
+#[cfg(not(all(feature = "cpuid", any(target_arch = "x86", target_arch = "x86_64"))))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_detect_cpuid {
+    ($feat: tt) => {
+        ::core::compile_error!("`dynamic(core)` and `cached(core)` require the `cpuid` feature")
+    };
+}
+
+/// Detects a feature by the auxiliary vector (internal; see the `auxv` module).
+#[cfg(all(
+    feature = "auxv",
+    target_os = "linux",
+    any(
+        target_arch = "arm",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "loongarch64",
+        target_arch = "s390x"
+    )
+))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_detect_auxv {
+    ($feat: tt) => {
+        (::core::cfg!(target_feature = $feat) || $crate::auxv::is_detected($feat))
+    };
+}
+
+/// Detects a feature by the auxiliary vector (internal; without the feature).
+#[cfg(not(all(
+    feature = "auxv",
+    target_os = "linux",
+    any(
+        target_arch = "arm",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "loongarch64",
+        target_arch = "s390x"
+    )
+)))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_detect_auxv {
+    ($feat: tt) => {
+        ::core::compile_error!("`dynamic(auxv)` and `cached(auxv)` require the `auxv` feature")
+    };
+}
+
+/// Reads the vector length in bits (internal; with the `vlen` feature).
+#[cfg(feature = "vlen")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_vector_length {
+    (vlen) => {
+        // SAFETY: Evaluated only after "v" is detected (by short-circuiting).
+        (unsafe { $crate::__private::vlen::vlen() })
+    };
+    (sve_vl) => {
+        // SAFETY: Evaluated only after "sve" is detected (by short-circuiting).
+        (unsafe { $crate::__private::vlen::sve_vl() })
+    };
+}
+
+/// Reads the vector length in bits (internal; without the feature).
+#[cfg(not(feature = "vlen"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_vector_length {
+    ($kind: ident) => {
+        ::core::compile_error!("dynamic `vlen` and `sve_vl` conditions require the `vlen` feature")
+    };
+}
+
+/// Ceiling of detected features on filling per-site caches
+/// (internal; with the `env-ceiling` feature).
+#[cfg(feature = "env-ceiling")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_ceiling {
+    ($detected: expr, $feat: tt) => {
+        ($detected && $crate::__private::ceiling::is_allowed($feat))
+    };
+}
+
+/// Ceiling of detected features on filling per-site caches
+/// (internal; without the feature, nothing is disabled).
+#[cfg(not(feature = "env-ceiling"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_ceiling {
+    ($detected: expr, $feat: tt) => {
+        $detected
+    };
+}
+
+/// Masking of features on dynamic dispatching (internal; with the feature).
+#[cfg(feature = "mask")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_mask {
+    // Detection result of a feature (unless masked).
+    (enabled $detected: expr, $feat: tt) => {
+        ($detected && $crate::__private::mask::is_enabled($feat))
+    };
+    // Cached index (the cache is bypassed while features are masked).
+    (cached ($cache: ident, $index: ident) $detect: expr) => {
+        let __tgtfeat_dispatch_masked = $crate::__private::mask::is_active();
+        if $index == 0 || __tgtfeat_dispatch_masked {
+            $index = $detect;
+            if !__tgtfeat_dispatch_masked {
+                $cache.store($index, ::core::sync::atomic::Ordering::Relaxed);
+            }
+        }
+    };
+}
+
+/// Masking of features on dynamic dispatching
+/// (internal; without the feature, nothing is masked).
+#[cfg(not(feature = "mask"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tgtfeat_dispatch_mask {
+    (enabled $detected: expr, $feat: tt) => {
+        $detected
+    };
+    (cached ($cache: ident, $index: ident) $detect: expr) => {
+        if $index == 0 {
+            $index = $detect;
+            $cache.store($index, ::core::sync::atomic::Ordering::Relaxed);
+        }
+    };
 }
@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Runtime masking of features (for testing).
+//!
+//! On dynamic dispatching, features masked by [`with_disabled`] are treated
+//! as absent on the current thread.  This makes it possible to exercise
+//! non-preferred clauses and fallback paths on a single host.
+//!
+//! ```
+//! use target_feature_dispatch::{mask, target_feature_dispatch};
+//!
+//! fn select() -> &'static str {
+//!     target_feature_dispatch! {
+//!         #[dynamic]
+//!         if family("x86") {
+//!             if "avx2" {
+//!                 "AVX2"
+//!             } else if "sse2" {
+//!                 "SSE2"
+//!             } else {
+//!                 "x86"
+//!             }
+//!         } else {
+//!             "fallback"
+//!         }
+//!     }
+//! }
+//!
+//! mask::with_disabled(&["avx2"], || {
+//!     assert_ne!(select(), "AVX2");
+//!     mask::with_disabled(&["sse2"], || {
+//!         assert!(matches!(select(), "x86" | "fallback"));
+//!     });
+//! });
+//! ```
+//!
+//! Note that:
+//!
+//! *   Masks only affect dynamic dispatching
+//!     (static dispatching is resolved on compilation).
+//! *   Masks do not follow implications of features.
+//!     For instance, masking `"avx"` does not mask `"avx2"`.
+//! *   While a mask is active on the current thread, caches of dynamic
+//!     dispatching (`#[cached]` and `dispatch_static!`) are bypassed.
+//!
+//! This module requires the `mask` feature (which enables `std`).
+//! Without it, dynamic dispatching does not check masks at all.
+
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use std::string::String;
+use std::vec::Vec;
+
+/// The number of active masks (on all threads).
+///
+/// Checked first so that dispatching is not slowed down without masks.
+pub(crate) static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+std::thread_local! {
+    /// Masked features on the current thread.
+    pub(crate) static DISABLED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Restores masked features on drop (including unwinding).
+struct Guard {
+    /// The number of masked features before [`with_disabled`].
+    len: usize,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        DISABLED.with(|disabled| disabled.borrow_mut().truncate(self.len));
+        ACTIVE.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Calls `f` while given features are treated as absent on
+/// dynamic dispatching on the current thread.
+///
+/// Calls can be nested (inner features are added to the outer mask).
+pub fn with_disabled<R>(features: &[&str], f: impl FnOnce() -> R) -> R {
+    let len = DISABLED.with(|disabled| {
+        let mut disabled = disabled.borrow_mut();
+        let len = disabled.len();
+        disabled.extend(features.iter().map(|&feature| String::from(feature)));
+        len
+    });
+    ACTIVE.fetch_add(1, Ordering::Relaxed);
+    let _guard = Guard { len };
+    f()
+}
@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Registry of named dispatching sites (for runtime reporting).
+//!
+//! An invocation of [`target_feature_dispatch!`](crate::target_feature_dispatch)
+//! with the `#[site = "NAME"]` pseudo-attribute registers itself in a
+//! link-time collected section.  [`sites()`] iterates over all registered
+//! sites in the program (including ones in dependent crates), which makes it
+//! possible to report which branch is taken on the current machine.
+//!
+//! ```
+//! use target_feature_dispatch::{sites, target_feature_dispatch};
+//!
+//! fn checksum(data: &[u8]) -> u32 {
+//!     target_feature_dispatch! {
+//!         #[dynamic]
+//!         #[site = "checksum"]
+//!         if family("x86") {
+//!             if "avx2" {
+//!                 data.iter().map(|&x| x as u32).sum()
+//!             } else {
+//!                 data.iter().map(|&x| x as u32).sum()
+//!             }
+//!         } else {
+//!             data.iter().map(|&x| x as u32).sum()
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(checksum(&[1, 2, 3]), 6);
+//! for site in sites::sites() {
+//!     println!("{}: {}", site.name(), site.selected());
+//!     site.for_each_label(|label| println!("  available: {label}"));
+//! }
+//! # #[cfg(any(target_os = "linux", target_os = "android"))]
+//! # assert!(sites::sites().any(|site| site.name() == "checksum"));
+//! ```
+//!
+//! Note that:
+//!
+//! *   Sites are registered by the linker (on ELF targets), not by
+//!     executing the invocation.  On other targets, [`sites()`] is empty.
+//! *   Labels are the same as the ones in `#[selected_label]` and
+//!     `#[all_branches]` (see the macro documentation).
+//! *   Resolving a site (and enumerating its labels) does not evaluate
+//!     bodies of the clauses.
+//! *   Each site is a static with the `#[unsafe(link_section)]` attribute
+//!     in the crate using `#[site = "NAME"]`.  Such crates can use
+//!     `#![deny(unsafe_code)]` but not `#![forbid(unsafe_code)]`.
+//!
+//! This module requires the `sites` feature.
+
+/// A registered dispatching site.
+#[derive(Debug)]
+pub struct Site {
+    /// The name of the site.
+    name: &'static str,
+    /// Resolves the label of the branch to be taken.
+    resolve: fn() -> &'static str,
+    /// Enumerates labels of the branches supported on the current machine.
+    labels: fn(&mut dyn FnMut(&'static str)),
+}
+
+impl Site {
+    /// Creates a site (used by the macro).
+    #[doc(hidden)]
+    pub const fn __new(
+        name: &'static str,
+        resolve: fn() -> &'static str,
+        labels: fn(&mut dyn FnMut(&'static str)),
+    ) -> Self {
+        Self {
+            name,
+            resolve,
+            labels,
+        }
+    }
+
+    /// Returns the name of the site.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the label of the branch taken on the current machine.
+    pub fn selected(&self) -> &'static str {
+        (self.resolve)()
+    }
+
+    /// Calls `f` with labels of all branches supported on the current
+    /// machine (ending with `"fallback"`), in the order of preference.
+    pub fn for_each_label(&self, mut f: impl FnMut(&'static str)) {
+        (self.labels)(&mut f)
+    }
+}
+
+/// The sentinel entry (so that the section always exists).
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[allow(unsafe_code)]
+#[used]
+#[unsafe(link_section = "target_feature_dispatch_sites")]
+static SENTINEL: Site = Site::__new("", || "fallback", |_| {});
+
+/// Returns registered sites in the program.
+///
+/// The order of the sites is unspecified.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[allow(unsafe_code)]
+pub fn sites() -> impl Iterator<Item = &'static Site> {
+    unsafe extern "C" {
+        /// The start of the section (defined by the linker).
+        #[link_name = "__start_target_feature_dispatch_sites"]
+        static START: u8;
+        /// The end of the section (defined by the linker).
+        #[link_name = "__stop_target_feature_dispatch_sites"]
+        static STOP: u8;
+    }
+    let sentinel: *const Site = core::hint::black_box(&SENTINEL);
+    let start = (&raw const START).cast::<Site>();
+    let stop = (&raw const STOP).cast::<Site>();
+    // SAFETY: the section only consists of `Site` entries
+    // (including the sentinel, so that the section is not empty).
+    let entries = unsafe {
+        core::slice::from_raw_parts(start, (stop as usize - start as usize) / size_of::<Site>())
+    };
+    entries
+        .iter()
+        .filter(move |&site| !core::ptr::eq(site, sentinel))
+}
+
+/// Returns registered sites in the program.
+///
+/// The registry is not supported on this target and
+/// this function returns an empty iterator.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn sites() -> impl Iterator<Item = &'static Site> {
+    core::iter::empty()
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+//! Zero-sized proof tokens of target features.
+//!
+//! A proof token is handed to a feature-specific clause with the
+//! `if "FEATURE" as TOKEN` syntax of [`target_feature_dispatch`](crate::target_feature_dispatch)
+//! and proves that the feature is available on the current CPU.
+//!
+//! Each token can be only created by [`new`](Avx2Token::new) which is a
+//! `#[target_feature]` function (safe to call only from a context with the
+//! feature enabled).  Functions accepting a token can rely on that the
+//! feature is available.
+//!
+//! Tokens are defined only for features of the target architecture
+//! (x86 and AArch64) which can be used with `#[target_feature]`
+//! since Rust 1.86.
+//!
+//! This module is available with the `proof-tokens` feature (MSRV 1.86).
+//!
+//! ```
+//! # #![allow(unsafe_code)]
+//! # #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+//! mod kernels {
+//!     use target_feature_dispatch::tokens::Avx2Token;
+//!
+//!     #[target_feature(enable = "avx2")]
+//!     fn process_data_with_avx2(data: &mut [u8]) {
+//!         // SIMD implementation using AVX2.
+//! #       let _ = data;
+//!     }
+//!
+//!     // A safe API for AVX2 (only callable with the proof token).
+//!     pub fn process_data(_token: Avx2Token, data: &mut [u8]) {
+//!         // SAFETY: The token proves that AVX2 is available.
+//!         unsafe { process_data_with_avx2(data) }
+//!     }
+//! }
+//!
+//! # #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+//! # {
+//! use target_feature_dispatch::target_feature_dispatch;
+//!
+//! let mut data = [0u8; 16];
+//! target_feature_dispatch! {
+//!     #[dynamic]
+//!     if family("x86") {
+//!         if "avx2" as token {
+//!             kernels::process_data(token, &mut data);
+//!         } else {
+//!             // Generic implementation.
+//!         }
+//!     } else {
+//!         // Generic implementation.
+//!     }
+//! }
+//! # }
+//! ```
+
+/// Zero-sized proof token of a target feature.
+///
+/// `INDEX` identifies the feature and is not a part of the public API.
+/// Use type aliases such as [`Avx2Token`] to name the token of a feature.
+#[derive(Clone, Copy, Debug)]
+pub struct FeatureToken<const INDEX: usize>(());
+
+/// Defines the list of features with proof tokens and their type aliases.
+macro_rules! define_tokens {
+    ($($feat: literal => $name: ident,)*) => {
+        /// Features with proof tokens on the target architecture.
+        pub(crate) const FEATURES: &[&str] = &[$($feat),*];
+
+        $(
+            #[doc = concat!("Proof token of the `", $feat, "` target feature.")]
+            pub type $name = FeatureToken<{ crate::__private::tokens::index($feat) }>;
+
+            impl $name {
+                #[doc = concat!("Creates a proof token of the `", $feat, "` target feature.")]
+                ///
+                /// # Safety
+                ///
+                /// Calling this function from a context without the feature
+                /// enabled requires `unsafe` and the caller must ensure that
+                /// the feature is available.
+                #[target_feature(enable = $feat)]
+                #[inline]
+                #[allow(clippy::new_without_default)]
+                pub fn new() -> Self {
+                    Self(())
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+define_tokens! {
+    "adx" => AdxToken,
+    "aes" => AesToken,
+    "avx" => AvxToken,
+    "avx2" => Avx2Token,
+    "bmi1" => Bmi1Token,
+    "bmi2" => Bmi2Token,
+    "cmpxchg16b" => Cmpxchg16bToken,
+    "f16c" => F16cToken,
+    "fma" => FmaToken,
+    "fxsr" => FxsrToken,
+    "lzcnt" => LzcntToken,
+    "movbe" => MovbeToken,
+    "pclmulqdq" => PclmulqdqToken,
+    "popcnt" => PopcntToken,
+    "rdrand" => RdrandToken,
+    "rdseed" => RdseedToken,
+    "sha" => ShaToken,
+    "sse" => SseToken,
+    "sse2" => Sse2Token,
+    "sse3" => Sse3Token,
+    "sse4.1" => Sse4_1Token,
+    "sse4.2" => Sse4_2Token,
+    "ssse3" => Ssse3Token,
+    "xsave" => XsaveToken,
+    "xsavec" => XsavecToken,
+    "xsaveopt" => XsaveoptToken,
+    "xsaves" => XsavesToken,
+}
+
+#[cfg(target_arch = "aarch64")]
+define_tokens! {
+    "aes" => AesToken,
+    "bf16" => Bf16Token,
+    "bti" => BtiToken,
+    "crc" => CrcToken,
+    "dit" => DitToken,
+    "dotprod" => DotprodToken,
+    "dpb" => DpbToken,
+    "dpb2" => Dpb2Token,
+    "f32mm" => F32mmToken,
+    "f64mm" => F64mmToken,
+    "fcma" => FcmaToken,
+    "fhm" => FhmToken,
+    "flagm" => FlagmToken,
+    "fp16" => Fp16Token,
+    "frintts" => FrinttsToken,
+    "i8mm" => I8mmToken,
+    "jsconv" => JsconvToken,
+    "lor" => LorToken,
+    "lse" => LseToken,
+    "mte" => MteToken,
+    "neon" => NeonToken,
+    "paca" => PacaToken,
+    "pacg" => PacgToken,
+    "pan" => PanToken,
+    "rand" => RandToken,
+    "ras" => RasToken,
+    "rcpc" => RcpcToken,
+    "rcpc2" => Rcpc2Token,
+    "rdm" => RdmToken,
+    "sb" => SbToken,
+    "sha2" => Sha2Token,
+    "sha3" => Sha3Token,
+    "sm4" => Sm4Token,
+    "spe" => SpeToken,
+    "ssbs" => SsbsToken,
+    "sve" => SveToken,
+    "sve2" => Sve2Token,
+    "sve2-aes" => Sve2AesToken,
+    "sve2-bitperm" => Sve2BitpermToken,
+    "sve2-sha3" => Sve2Sha3Token,
+    "sve2-sm4" => Sve2Sm4Token,
+    "vh" => VhToken,
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+define_tokens! {}
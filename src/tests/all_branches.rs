@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+use target_feature_dispatch::target_feature_dispatch;
+
+fn sum_by_chunks(values: &[u32], chunk: usize) -> u32 {
+    values.chunks(chunk).map(|c| c.iter().sum::<u32>()).sum()
+}
+
+fn sum_all(values: &[u32]) -> Vec<(&'static str, u32)> {
+    target_feature_dispatch! {
+        #[all_branches]
+        if family("x86") {
+            if "avx2" {
+                sum_by_chunks(values, 8)
+            } else if "sse4.1" && "popcnt" {
+                sum_by_chunks(values, 4)
+            } else if "sse2" {
+                sum_by_chunks(values, 2)
+            }
+        } else if family("aarch64") {
+            if "sve" {
+                sum_by_chunks(values, 16)
+            } else {
+                sum_by_chunks(values, 4)
+            }
+        } else {
+            values.iter().sum()
+        }
+    }
+    .collect()
+}
+
+#[test]
+fn all_branches_dynamic() {
+    let values: Vec<u32> = (0..100).collect();
+    let results = sum_all(&values);
+    // All branches return the same result.
+    for &(label, result) in &results {
+        assert_eq!(result, 4950, "branch {label}");
+    }
+    // The fallback is always evaluated (last).
+    assert_eq!(results.last().unwrap().0, "fallback");
+    let labels: Vec<&str> = results.iter().map(|&(label, _)| label).collect();
+    #[cfg(target_arch = "x86_64")]
+    {
+        assert!(labels.contains(&"sse2"));
+        assert_eq!(
+            labels.contains(&"avx2"),
+            std::arch::is_x86_feature_detected!("avx2")
+        );
+        assert_eq!(
+            labels.contains(&"\"sse4.1\" && \"popcnt\""),
+            std::arch::is_x86_feature_detected!("sse4.1")
+                && std::arch::is_x86_feature_detected!("popcnt")
+        );
+    }
+    #[cfg(target_arch = "aarch64")]
+    assert_eq!(
+        labels.contains(&"sve"),
+        std::arch::is_aarch64_feature_detected!("sve")
+    );
+    #[cfg(not(any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "arm64ec"
+    )))]
+    assert_eq!(labels, ["fallback"]);
+}
+
+#[test]
+fn all_branches_static() {
+    let labels: Vec<&str> = target_feature_dispatch! {
+        #[all_branches]
+        #[static]
+        if family("x86") {
+            if "avx2" {
+                2
+            } else if "sse2" {
+                1
+            }
+        } else {
+            0
+        }
+    }
+    .map(|(label, _)| label)
+    .collect();
+    let mut expected = Vec::new();
+    if cfg!(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "avx2"
+    )) {
+        expected.push("avx2");
+    }
+    if cfg!(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    )) {
+        expected.push("sse2");
+    }
+    expected.push("fallback");
+    assert_eq!(labels, expected);
+}
+
+#[test]
+fn all_branches_single() {
+    // Architecture-only clauses and the fallback of the root
+    // are the only branch.
+    let results: Vec<(&str, u32)> = target_feature_dispatch! {
+        #[all_branches]
+        if family("x86") {
+            (1)
+        } else {
+            0
+        }
+    }
+    .collect();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].0, "fallback");
+    assert_eq!(
+        results[0].1 == 1,
+        cfg!(any(target_arch = "x86", target_arch = "x86_64"))
+    );
+}
@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+use target_feature_dispatch::{define_arch_class, define_arch_family, target_feature_dispatch};
+
+define_arch_family!(
+    /// 64-bit architectures with dynamic dispatching.
+    pub AES64 = "x86_64" || family("aarch64")
+);
+define_arch_family!(SIMD64 = family(AES64) || "riscv64");
+define_arch_class!(LOONG_RISCV = "loongarch64" || family("riscv"));
+
+mod arch {
+    use target_feature_dispatch::define_arch_family;
+
+    define_arch_family!(pub(crate) X86_OR_SIMD64 = "x86" || family(SIMD64));
+}
+
+mod inner {
+    use target_feature_dispatch::target_feature_dispatch;
+
+    use crate::arch::X86_OR_SIMD64;
+
+    pub fn select() -> &'static str {
+        target_feature_dispatch! {
+            if family(X86_OR_SIMD64) {
+                ("x86 or 64-bit")
+            } else {
+                "others"
+            }
+        }
+    }
+}
+
+fn is_simd64() -> bool {
+    cfg!(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "arm64ec",
+        target_arch = "riscv64"
+    ))
+}
+
+#[test]
+fn user_defined_family() {
+    fn select(dynamic: bool) -> &'static str {
+        if dynamic {
+            target_feature_dispatch! {
+                #[dynamic]
+                if family(AES64) {
+                    if "aes" {
+                        "AES64+AES"
+                    } else {
+                        "AES64"
+                    }
+                } else {
+                    "others"
+                }
+            }
+        } else {
+            target_feature_dispatch! {
+                if family(AES64) {
+                    if "aes" {
+                        "AES64+AES"
+                    } else {
+                        "AES64"
+                    }
+                } else {
+                    "others"
+                }
+            }
+        }
+    }
+    let is_aes64 = cfg!(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "arm64ec"
+    ));
+    assert_eq!(select(true) != "others", is_aes64);
+    assert_eq!(select(false) != "others", is_aes64);
+    // Dynamic dispatching never selects less than static one.
+    if select(false) == "AES64+AES" {
+        assert_eq!(select(true), "AES64+AES");
+    }
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(
+        select(true) == "AES64+AES",
+        std::arch::is_x86_feature_detected!("aes")
+    );
+    let is_x86_or_simd64 = is_simd64() || cfg!(target_arch = "x86");
+    assert_eq!(inner::select() != "others", is_x86_or_simd64);
+}
+
+#[test]
+fn user_defined_class() {
+    let value = target_feature_dispatch! {
+        #[dynamic]
+        if class(LOONG_RISCV) {
+            if "lsx" || "v" {
+                2
+            } else {
+                1
+            }
+        } else if "x86" || family(SIMD64) {
+            (3)
+        } else {
+            0
+        }
+    };
+    let is_loong_riscv = cfg!(any(
+        target_arch = "loongarch64",
+        target_arch = "riscv32",
+        target_arch = "riscv64"
+    ));
+    assert_eq!(value == 1 || value == 2, is_loong_riscv);
+    assert_eq!(
+        value == 3,
+        !is_loong_riscv && (is_simd64() || cfg!(target_arch = "x86"))
+    );
+}
+
+#[test]
+fn user_defined_options() {
+    // Static dispatching except on AES64.
+    let value = target_feature_dispatch! {
+        #[dynamic(family(AES64))]
+        if family("x86") {
+            if "avx2" {
+                2
+            } else {
+                1
+            }
+        } else {
+            0
+        }
+    };
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(value == 2, std::arch::is_x86_feature_detected!("avx2"));
+    #[cfg(target_arch = "x86")]
+    assert_eq!(value == 2, cfg!(target_feature = "avx2"));
+    let _ = value;
+}
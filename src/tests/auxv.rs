@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+use target_feature_dispatch::target_feature_dispatch;
+
+fn select_neon() -> u32 {
+    target_feature_dispatch! {
+        #[dynamic(auxv)]
+        if "arm" {
+            if "neon" {
+                2
+            } else {
+                1
+            }
+        } else if family("x86") {
+            if "avx2" {
+                2
+            } else {
+                1
+            }
+        } else {
+            0
+        }
+    }
+}
+
+#[test]
+fn other_architectures_unaffected() {
+    // `auxv` does not enable dynamic dispatching on other architectures.
+    let value = select_neon();
+    if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        assert_eq!(value, if cfg!(target_feature = "avx2") { 2 } else { 1 });
+    }
+}
+
+#[cfg(all(feature = "auxv", target_os = "linux", target_arch = "arm"))]
+#[test]
+fn consistent_with_detector() {
+    let detected =
+        cfg!(target_feature = "neon") || target_feature_dispatch::auxv::is_detected("neon");
+    assert_eq!(select_neon(), if detected { 2 } else { 1 });
+}
+
+#[cfg(all(
+    feature = "auxv",
+    target_os = "linux",
+    any(
+        target_arch = "arm",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "loongarch64",
+        target_arch = "s390x"
+    )
+))]
+#[test]
+fn unknown_features() {
+    assert!(!target_feature_dispatch::auxv::is_detected("unknown"));
+}
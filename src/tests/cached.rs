@@ -0,0 +1,237 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+use target_feature_dispatch::target_feature_dispatch;
+
+fn select_static() -> &'static str {
+    target_feature_dispatch! {
+        if family("aarch64") {
+            if "sve2" {
+                "Arm64+SVE2"
+            } else if "sve" {
+                "Arm64+SVE"
+            }
+        } else if family("x86") {
+            if "avx2" {
+                "x86+AVX2"
+            } else if "sse4.1" && "popcnt" {
+                "x86+SSE4.1+POPCNT"
+            } else if "sse2" {
+                "x86+SSE2"
+            }
+        } else {
+            "others"
+        }
+    }
+}
+
+fn select_dynamic() -> &'static str {
+    target_feature_dispatch! {
+        #[dynamic]
+        if family("aarch64") {
+            if "sve2" {
+                "Arm64+SVE2"
+            } else if "sve" {
+                "Arm64+SVE"
+            }
+        } else if family("x86") {
+            if "avx2" {
+                "x86+AVX2"
+            } else if "sse4.1" && "popcnt" {
+                "x86+SSE4.1+POPCNT"
+            } else if "sse2" {
+                "x86+SSE2"
+            }
+        } else {
+            "others"
+        }
+    }
+}
+
+fn select_cached() -> &'static str {
+    target_feature_dispatch! {
+        #[cached]
+        if family("aarch64") {
+            if "sve2" {
+                "Arm64+SVE2"
+            } else if "sve" {
+                "Arm64+SVE"
+            }
+        } else if family("x86") {
+            if "avx2" {
+                "x86+AVX2"
+            } else if "sse4.1" && "popcnt" {
+                "x86+SSE4.1+POPCNT"
+            } else if "sse2" {
+                "x86+SSE2"
+            }
+        } else {
+            "others"
+        }
+    }
+}
+
+fn select_cached_with_local_fallback() -> &'static str {
+    target_feature_dispatch! {
+        #[cached]
+        if family("aarch64") {
+            if "sve2" {
+                "Arm64+SVE2"
+            } else if "sve" {
+                "Arm64+SVE"
+            } else {
+                "Arm64"
+            }
+        } else if family("x86") {
+            if "avx2" {
+                "x86+AVX2"
+            } else if "sse4.1" && "popcnt" {
+                "x86+SSE4.1+POPCNT"
+            } else if "sse2" {
+                "x86+SSE2"
+            } else {
+                "x86"
+            }
+        } else {
+            "others"
+        }
+    }
+}
+
+#[test]
+fn cached_matches_dynamic() {
+    let expected = select_dynamic();
+    // The first call performs feature detection and
+    // succeeding calls only use the cached index.
+    for _ in 0..4 {
+        assert_eq!(select_cached(), expected);
+    }
+}
+
+#[test]
+fn cached_matches_dynamic_with_local_fallback() {
+    let expected = match select_dynamic() {
+        "others" if cfg!(any(target_arch = "aarch64", target_arch = "arm64ec")) => "Arm64",
+        "others" if cfg!(any(target_arch = "x86", target_arch = "x86_64")) => "x86",
+        value => value,
+    };
+    for _ in 0..4 {
+        assert_eq!(select_cached_with_local_fallback(), expected);
+    }
+}
+
+#[test]
+fn cached_option_overrides() {
+    // `dynamic` after `cached` disables caching but still performs
+    // dynamic dispatching.  The result must be the same anyway.
+    let value: &str = target_feature_dispatch! {
+        #[cached]
+        #[cfg_attr(all(), dynamic)]
+        if family("x86") {
+            if "avx2" {
+                "x86+AVX2"
+            } else if "sse4.1" && "popcnt" {
+                "x86+SSE4.1+POPCNT"
+            } else if "sse2" {
+                "x86+SSE2"
+            }
+        } else if family("aarch64") {
+            if "sve2" {
+                "Arm64+SVE2"
+            } else if "sve" {
+                "Arm64+SVE"
+            }
+        } else {
+            "others"
+        }
+    };
+    assert_eq!(value, select_dynamic());
+    // `cfg_attr(..., cached)` re-enables caching.
+    for _ in 0..2 {
+        let value: &str = target_feature_dispatch! {
+            #[static]
+            #[cfg_attr(all(), cached)]
+            if family("x86") {
+                if "avx2" {
+                    "x86+AVX2"
+                } else if "sse4.1" && "popcnt" {
+                    "x86+SSE4.1+POPCNT"
+                } else if "sse2" {
+                    "x86+SSE2"
+                }
+            } else if family("aarch64") {
+                if "sve2" {
+                    "Arm64+SVE2"
+                } else if "sve" {
+                    "Arm64+SVE"
+                }
+            } else {
+                "others"
+            }
+        };
+        assert_eq!(value, select_dynamic());
+    }
+}
+
+#[test]
+fn per_architecture_options() {
+    let is_x86 = cfg!(any(target_arch = "x86", target_arch = "x86_64"));
+    // Dynamic dispatching only on x86.
+    let value: &str = target_feature_dispatch! {
+        #[dynamic(family("x86"))]
+        if family("aarch64") {
+            if "sve2" {
+                "Arm64+SVE2"
+            } else if "sve" {
+                "Arm64+SVE"
+            }
+        } else if family("x86") {
+            if "avx2" {
+                "x86+AVX2"
+            } else if "sse4.1" && "popcnt" {
+                "x86+SSE4.1+POPCNT"
+            } else if "sse2" {
+                "x86+SSE2"
+            }
+        } else {
+            "others"
+        }
+    };
+    let expected = if is_x86 {
+        select_dynamic()
+    } else {
+        select_static()
+    };
+    assert_eq!(value, expected);
+    // Cached dispatching except on x86 (static) and RISC-V (dynamic).
+    for _ in 0..2 {
+        let value: &str = target_feature_dispatch! {
+            #[cached]
+            #[static(family("x86"))]
+            #[cfg_attr(all(), dynamic(family("riscv")))]
+            if family("aarch64") {
+                if "sve2" {
+                    "Arm64+SVE2"
+                } else if "sve" {
+                    "Arm64+SVE"
+                }
+            } else if family("x86") {
+                if "avx2" {
+                    "x86+AVX2"
+                } else if "sse4.1" && "popcnt" {
+                    "x86+SSE4.1+POPCNT"
+                } else if "sse2" {
+                    "x86+SSE2"
+                }
+            } else {
+                "others"
+            }
+        };
+        let expected = if is_x86 {
+            select_static()
+        } else {
+            select_dynamic()
+        };
+        assert_eq!(value, expected);
+    }
+}
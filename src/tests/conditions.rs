@@ -0,0 +1,486 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+use target_feature_dispatch::target_feature_dispatch;
+
+#[test]
+fn static_conditions() {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        let value = target_feature_dispatch! {
+            if family("x86") {
+                if ("sse4.1" && "popcnt") || "avx2" {
+                    1
+                } else if "sse2" && !"avx" {
+                    2
+                } else if !("sse2" || "sse") {
+                    3
+                } else {
+                    4
+                }
+            } else {
+                unreachable!()
+            }
+        };
+        let expected = if cfg!(any(
+            all(target_feature = "sse4.1", target_feature = "popcnt"),
+            target_feature = "avx2"
+        )) {
+            1
+        } else if cfg!(all(target_feature = "sse2", not(target_feature = "avx"))) {
+            2
+        } else if cfg!(not(any(target_feature = "sse2", target_feature = "sse"))) {
+            3
+        } else {
+            4
+        };
+        assert_eq!(value, expected);
+    }
+    #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec"))]
+    {
+        let value = target_feature_dispatch! {
+            if family("aarch64") {
+                if "neon" && !"sve" {
+                    1
+                } else if "sve" || "sve2" {
+                    2
+                } else {
+                    3
+                }
+            } else {
+                unreachable!()
+            }
+        };
+        let expected = if cfg!(all(target_feature = "neon", not(target_feature = "sve"))) {
+            1
+        } else if cfg!(any(target_feature = "sve", target_feature = "sve2")) {
+            2
+        } else {
+            3
+        };
+        assert_eq!(value, expected);
+    }
+}
+
+#[test]
+fn dynamic_conditions() {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        use std::arch::is_x86_feature_detected;
+        let value = target_feature_dispatch! {
+            #[dynamic]
+            if family("x86") {
+                if "avx512f" || "avx2" && !"fma" {
+                    1
+                } else if !(!"sse2") && ("avx" || !"sse4.2") {
+                    2
+                } else {
+                    3
+                }
+            } else {
+                unreachable!()
+            }
+        };
+        let expected = if is_x86_feature_detected!("avx512f")
+            || is_x86_feature_detected!("avx2") && !is_x86_feature_detected!("fma")
+        {
+            1
+        } else if is_x86_feature_detected!("sse2")
+            && (is_x86_feature_detected!("avx") || !is_x86_feature_detected!("sse4.2"))
+        {
+            2
+        } else {
+            3
+        };
+        assert_eq!(value, expected);
+    }
+    #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec"))]
+    {
+        use std::arch::is_aarch64_feature_detected;
+        let value = target_feature_dispatch! {
+            #[dynamic]
+            if family("aarch64") {
+                if "neon" && !("sve" || "sve2") {
+                    1
+                } else {
+                    2
+                }
+            } else {
+                unreachable!()
+            }
+        };
+        let expected = if is_aarch64_feature_detected!("neon")
+            && !(is_aarch64_feature_detected!("sve") || is_aarch64_feature_detected!("sve2"))
+        {
+            1
+        } else {
+            2
+        };
+        assert_eq!(value, expected);
+    }
+}
+
+#[test]
+fn cached_conditions() {
+    fn select_dynamic() -> u32 {
+        target_feature_dispatch! {
+            #[dynamic]
+            if family("x86") {
+                if !"avx" && "sse2" {
+                    1
+                } else if ("avx" && "fma") || "avx512f" {
+                    2
+                }
+            } else if family("aarch64") {
+                if !"sve" {
+                    1
+                } else if ("sve" && "sve2") || "sme" {
+                    2
+                }
+            } else {
+                0
+            }
+        }
+    }
+    fn select_cached() -> u32 {
+        target_feature_dispatch! {
+            #[cached]
+            if family("x86") {
+                if !"avx" && "sse2" {
+                    1
+                } else if ("avx" && "fma") || "avx512f" {
+                    2
+                }
+            } else if family("aarch64") {
+                if !"sve" {
+                    1
+                } else if ("sve" && "sve2") || "sme" {
+                    2
+                }
+            } else {
+                0
+            }
+        }
+    }
+    let expected = select_dynamic();
+    for _ in 0..4 {
+        assert_eq!(select_cached(), expected);
+    }
+}
+
+#[test]
+fn named_levels() {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        use std::arch::is_x86_feature_detected;
+        let value = target_feature_dispatch! {
+            if family("x86") {
+                if level("x86-64-v3") {
+                    3
+                } else if level("x86-64-v2") && !"avx" {
+                    2
+                } else {
+                    1
+                }
+            } else {
+                unreachable!()
+            }
+        };
+        let expected = if cfg!(all(
+            target_feature = "avx",
+            target_feature = "avx2",
+            target_feature = "bmi1",
+            target_feature = "bmi2",
+            target_feature = "f16c",
+            target_feature = "fma",
+            target_feature = "lzcnt",
+            target_feature = "movbe",
+            target_feature = "xsave",
+            target_feature = "cmpxchg16b",
+            target_feature = "fxsr",
+            target_feature = "lahfsahf",
+            target_feature = "popcnt",
+            target_feature = "sse",
+            target_feature = "sse2",
+            target_feature = "sse3",
+            target_feature = "sse4.1",
+            target_feature = "sse4.2",
+            target_feature = "ssse3",
+        )) {
+            3
+        } else if cfg!(all(
+            target_feature = "cmpxchg16b",
+            target_feature = "fxsr",
+            target_feature = "lahfsahf",
+            target_feature = "popcnt",
+            target_feature = "sse",
+            target_feature = "sse2",
+            target_feature = "sse3",
+            target_feature = "sse4.1",
+            target_feature = "sse4.2",
+            target_feature = "ssse3",
+            not(target_feature = "avx"),
+        )) {
+            2
+        } else {
+            1
+        };
+        assert_eq!(value, expected);
+        let value = target_feature_dispatch! {
+            #[dynamic]
+            if family("x86") {
+                if level("x86-64-v4") {
+                    4
+                } else if level("x86-64-v3") {
+                    3
+                } else if level("x86-64-v2") && !"avx" {
+                    2
+                } else {
+                    1
+                }
+            } else {
+                unreachable!()
+            }
+        };
+        let v2 = is_x86_feature_detected!("cmpxchg16b")
+            && is_x86_feature_detected!("fxsr")
+            && is_x86_feature_detected!("popcnt")
+            && is_x86_feature_detected!("sse")
+            && is_x86_feature_detected!("sse2")
+            && is_x86_feature_detected!("sse3")
+            && is_x86_feature_detected!("sse4.1")
+            && is_x86_feature_detected!("sse4.2")
+            && is_x86_feature_detected!("ssse3");
+        let v3 = v2
+            && is_x86_feature_detected!("avx")
+            && is_x86_feature_detected!("avx2")
+            && is_x86_feature_detected!("bmi1")
+            && is_x86_feature_detected!("bmi2")
+            && is_x86_feature_detected!("f16c")
+            && is_x86_feature_detected!("fma")
+            && is_x86_feature_detected!("lzcnt")
+            && is_x86_feature_detected!("movbe")
+            && is_x86_feature_detected!("xsave");
+        let v4 = v3
+            && is_x86_feature_detected!("avx512bw")
+            && is_x86_feature_detected!("avx512cd")
+            && is_x86_feature_detected!("avx512dq")
+            && is_x86_feature_detected!("avx512f")
+            && is_x86_feature_detected!("avx512vl");
+        let expected = if v4 {
+            4
+        } else if v3 {
+            3
+        } else if v2 && !is_x86_feature_detected!("avx") {
+            2
+        } else {
+            1
+        };
+        assert_eq!(value, expected);
+    }
+    #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec"))]
+    {
+        use std::arch::is_aarch64_feature_detected;
+        let value = target_feature_dispatch! {
+            #[dynamic]
+            if family("aarch64") {
+                if level("armv8.2-a") {
+                    2
+                } else if level("armv8.1-a") {
+                    1
+                } else {
+                    0
+                }
+            } else {
+                unreachable!()
+            }
+        };
+        let v81 = is_aarch64_feature_detected!("neon")
+            && is_aarch64_feature_detected!("crc")
+            && is_aarch64_feature_detected!("lse")
+            && is_aarch64_feature_detected!("rdm");
+        let v82 = v81 && is_aarch64_feature_detected!("dpb");
+        let expected = if v82 {
+            2
+        } else if v81 {
+            1
+        } else {
+            0
+        };
+        assert_eq!(value, expected);
+    }
+}
+
+#[test]
+fn profiles() {
+    let value = target_feature_dispatch! {
+        if family("riscv") {
+            if profile("rva23u64") {
+                23
+            } else if profile("rva22u64") && !"v" {
+                22
+            } else if profile("rva20u64") {
+                20
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    };
+    // RVA profiles are 64-bit only.
+    #[cfg(not(target_arch = "riscv64"))]
+    assert_eq!(value, 0);
+    let _ = value;
+    let value = target_feature_dispatch! {
+        #[dynamic]
+        if family("riscv") {
+            if profile("rva23u64") {
+                23
+            } else if profile("rva22u64") {
+                22
+            } else if profile("rva20u64") {
+                20
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    };
+    // Mandatory extensions which cannot be detected at runtime
+    // (e.g. Ziccif) are checked statically.
+    #[cfg(not(all(
+        target_arch = "riscv64",
+        target_feature = "ziccif",
+        target_feature = "ziccrse",
+        target_feature = "ziccamoa",
+        target_feature = "zicclsm",
+    )))]
+    assert_eq!(value, 0);
+    let _ = value;
+}
+
+#[test]
+#[deny(deprecated)]
+fn reachable_clauses() {
+    // Clauses ordered from the most specific to the least specific
+    // must not be reported as unreachable.
+    let value = target_feature_dispatch! {
+        if family("x86") {
+            if level("x86-64-v4") {
+                4
+            } else if "avx512f" || level("x86-64-v3") {
+                3
+            } else if "sse4.1" && "popcnt" {
+                2
+            } else if "sse4.1" || "popcnt" {
+                1
+            } else {
+                0
+            }
+        } else if family("aarch64") {
+            if "sve2" && "sha3" {
+                2
+            } else if "sve" {
+                1
+            } else {
+                0
+            }
+        } else if family("riscv") {
+            if "v" && "zvbb" {
+                2
+            } else if "zve64d" {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    };
+    assert!(value <= 4);
+}
+
+#[test]
+fn cfg_clauses() {
+    // Configured-out clauses are removed (including their bodies)
+    // and the dispatching falls through to the next clause.
+    fn select(dynamic: bool) -> u32 {
+        if dynamic {
+            target_feature_dispatch! {
+                #[dynamic]
+                #[cfg(any())]
+                if family("x86") {
+                    does_not_exist()
+                } else #[cfg(all())] if family("x86") || family("aarch64") {
+                    #[cfg(any())]
+                    if "avx512f" || "sve2" {
+                        does_not_exist()
+                    } else #[cfg(all())] #[cfg(not(any()))] if "sse2" || "neon" {
+                        2
+                    } else #[cfg(any())] if "sse" {
+                        does_not_exist()
+                    } else {
+                        1
+                    }
+                } else #[cfg(any())] if family("riscv") {
+                    does_not_exist()
+                } else {
+                    0
+                }
+            }
+        } else {
+            target_feature_dispatch! {
+                if family("x86") || family("aarch64") {
+                    #[cfg(any())]
+                    if "sse2" || "neon" {
+                        does_not_exist()
+                    }
+                } else {
+                    0
+                }
+            }
+        }
+    }
+    let is_x86_or_aarch64 = cfg!(any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "arm64ec"
+    ));
+    assert_eq!(select(false), 0);
+    assert!(select(true) <= 2);
+    assert_eq!(select(true) == 0, !is_x86_or_aarch64);
+}
+
+#[test]
+fn families_with_multiple_detection_macros() {
+    // Each member of the family uses its own feature detection macro.
+    let value = target_feature_dispatch! {
+        #[dynamic]
+        if family("arm") {
+            if "aes" {
+                3
+            } else {
+                2
+            }
+        } else if family("powerpc") {
+            if "altivec" {
+                1
+            }
+        } else if family("mips") {
+            if "msa" {
+                1
+            }
+        } else {
+            0
+        }
+    };
+    let is_arm = cfg!(any(
+        target_arch = "aarch64",
+        target_arch = "arm64ec",
+        target_arch = "arm"
+    ));
+    assert_eq!(value >= 2, is_arm);
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+#![cfg(all(feature = "cpuid", any(target_arch = "x86", target_arch = "x86_64")))]
+
+use target_feature_dispatch::{cpuid, target_feature_dispatch};
+
+fn select_std() -> u32 {
+    target_feature_dispatch! {
+        #[dynamic]
+        if family("x86") {
+            if "avx512f" && "avx512bw" {
+                4
+            } else if "avx2" && "fma" {
+                3
+            } else if "sse4.2" || "popcnt" {
+                2
+            } else if "sse2" {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    }
+}
+
+fn select_core() -> u32 {
+    target_feature_dispatch! {
+        #[dynamic(core)]
+        if family("x86") {
+            if "avx512f" && "avx512bw" {
+                4
+            } else if "avx2" && "fma" {
+                3
+            } else if "sse4.2" || "popcnt" {
+                2
+            } else if "sse2" {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    }
+}
+
+fn select_cached_core() -> u32 {
+    target_feature_dispatch! {
+        #[cached(core)]
+        if family("x86") {
+            if "avx512f" && "avx512bw" {
+                4
+            } else if "avx2" && "fma" {
+                3
+            } else if "sse4.2" || "popcnt" {
+                2
+            } else if "sse2" {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    }
+}
+
+#[test]
+fn same_as_std() {
+    assert_eq!(select_core(), select_std());
+    assert_eq!(select_cached_core(), select_std());
+    assert_eq!(select_cached_core(), select_std());
+}
+
+#[test]
+fn is_detected() {
+    macro_rules! check {
+        ($($feat: tt),+) => {
+            $(
+                assert_eq!(
+                    cpuid::is_detected($feat),
+                    std::arch::is_x86_feature_detected!($feat),
+                    "feature: {}", $feat
+                );
+            )+
+        };
+    }
+    check!(
+        "sse2", "sse4.1", "sse4.2", "popcnt", "avx", "avx2", "fma", "bmi2", "avx512f", "avx512vl",
+        "vaes", "gfni"
+    );
+    assert!(!cpuid::is_detected("unknown"));
+}
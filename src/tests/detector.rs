@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+#![allow(unsafe_code)]
+
+// Detectors below may report features not available on the host.
+// This is fine only because no clause depends on those features.
+
+use target_feature_dispatch::target_feature_dispatch;
+
+// Reports only "avx2" (macro-based detector).
+macro_rules! detect_avx2_only {
+    ($feat: tt) => {
+        $feat == "avx2"
+    };
+}
+
+// Reports nothing (function-based detector).
+fn detect_nothing(_feature: &str) -> bool {
+    false
+}
+
+mod detectors {
+    // Reports everything.
+    macro_rules! detect_all {
+        ($feat: tt) => {
+            true
+        };
+    }
+    pub(crate) use detect_all;
+}
+
+#[test]
+fn macro_detector() {
+    let value: u32 = target_feature_dispatch! {
+        #[dynamic]
+        #[detector(unsafe detect_avx2_only)]
+        if family("x86") {
+            if "avx512f" {
+                3
+            } else if "avx2" {
+                2
+            } else if "sse2" {
+                1
+            }
+        } else if family("aarch64") {
+            if "sve" {
+                1
+            }
+        } else {
+            0
+        }
+    };
+    let expected = if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        2
+    } else {
+        0
+    };
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn function_detector() {
+    let value: u32 = target_feature_dispatch! {
+        #[dynamic]
+        #[detector(unsafe fn detect_nothing)]
+        if family("x86") {
+            if "avx2" {
+                2
+            } else if "sse2" {
+                1
+            } else {
+                // Explicit fallback (without it, the static chain is used).
+                0
+            }
+        } else if family("aarch64") {
+            if "sve" {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    };
+    assert_eq!(value, 0);
+}
+
+#[test]
+fn per_family_detector() {
+    // The last matching detector is applied.
+    let value: u32 = target_feature_dispatch! {
+        #[dynamic]
+        #[detector(unsafe detectors::detect_all)]
+        #[detector(family("aarch64") => unsafe fn detect_nothing)]
+        #[cfg_attr(any(), detector(family("x86") => unsafe fn detect_nothing))]
+        if family("x86") {
+            if "avx2" {
+                2
+            }
+        } else if family("aarch64") {
+            if "sve" {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    };
+    let expected = if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        2
+    } else {
+        0
+    };
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn static_dispatch_unaffected() {
+    // Without `#[dynamic]`, the detector is not used.
+    let value: u32 = target_feature_dispatch! {
+        #[detector(unsafe fn detect_nothing)]
+        if family("x86") {
+            if "sse2" {
+                1
+            }
+        } else {
+            0
+        }
+    };
+    let expected = if cfg!(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    )) {
+        1
+    } else {
+        0
+    };
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn cached_detector() {
+    fn select() -> u32 {
+        target_feature_dispatch! {
+            #[cached]
+            #[detector(unsafe detect_avx2_only)]
+            if family("x86") {
+                if "avx2" {
+                    2
+                } else if "sse2" {
+                    1
+                }
+            } else {
+                0
+            }
+        }
+    }
+    let expected = if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        2
+    } else {
+        0
+    };
+    assert_eq!(select(), expected);
+    assert_eq!(select(), expected);
+}
+
+// Reports only a feature unknown to this crate (function-based detector).
+fn detect_unknown_only(feature: &str) -> bool {
+    feature == "x-unknown-feature"
+}
+
+// The feature is unknown to the compiler, too.
+#[allow(unexpected_cfgs)]
+#[test]
+fn unchecked_feature() {
+    let value: u32 = target_feature_dispatch! {
+        #[dynamic]
+        #[detector(unsafe fn detect_unknown_only)]
+        if family("x86") {
+            if unchecked("x-unknown-feature") && !"avx512f" {
+                2
+            } else if "sse2" {
+                1
+            }
+        } else {
+            0
+        }
+    };
+    let expected = if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        2
+    } else {
+        0
+    };
+    assert_eq!(value, expected);
+}
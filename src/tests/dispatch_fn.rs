@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+use target_feature_dispatch::target_feature_dispatch_fn;
+
+fn sum_naive(values: &[u32], init: u32) -> u32 {
+    values.iter().fold(init, |a, b| a.wrapping_add(*b))
+}
+
+target_feature_dispatch_fn! {
+    if family("x86") {
+        "avx2",
+        "sse4.1" && "popcnt",
+        "sse2",
+    } else if family("aarch64") {
+        "sve2", "neon"
+    }
+    /// Static dispatching.
+    fn sum_static(values: &[u32], init: u32) -> u32 {
+        values.iter().fold(init, |a, b| a.wrapping_add(*b))
+    }
+}
+
+target_feature_dispatch_fn! {
+    #[dynamic]
+    if family("x86") {
+        "avx2",
+        "sse4.1" && "popcnt",
+        "sse2",
+    } else if family("aarch64") {
+        "sve2", "neon"
+    }
+    /// Dynamic dispatching.
+    #[inline]
+    pub(crate) fn sum_dynamic(values: &[u32], init: u32) -> u32 {
+        values.iter().fold(init, |a, b| a.wrapping_add(*b))
+    }
+}
+
+target_feature_dispatch_fn! {
+    #[cached]
+    if family("x86") {
+        "avx2" && "fma",
+    }
+    fn sum_cached(values: &[u32], init: u32,) -> u32 {
+        values.iter().fold(init, |a, b| a.wrapping_add(*b))
+    }
+}
+
+target_feature_dispatch_fn! {
+    #[cfg_non_fallback(any())]
+    if family("x86") {
+        "avx2",
+    }
+    fn sum_fallback_only(values: &[u32], init: u32) -> u32 {
+        values.iter().fold(init, |a, b| a.wrapping_add(*b))
+    }
+}
+
+target_feature_dispatch_fn! {
+    #[dynamic]
+    if family("x86") {
+        "avx2",
+    }
+    fn no_return_value(value: &mut u32) {
+        *value += 1;
+    }
+}
+
+target_feature_dispatch_fn! {
+    #[dynamic]
+    if family("x86") {
+        "avx2",
+    } else if family("aarch64") {
+        "neon"
+    }
+    /// Items inside the body (duplicated per clone).
+    fn with_inner_items(value: u32) -> u32 {
+        const OFFSET: u32 = 2;
+        fn double(x: u32) -> u32 {
+            x * 2
+        }
+        double(value) + OFFSET
+    }
+}
+
+#[test]
+fn dispatch_fn_results() {
+    let values: Vec<u32> = (0..1000).map(|x| x * 7 + 3).collect();
+    let expected = sum_naive(&values, 5);
+    assert_eq!(sum_static(&values, 5), expected);
+    assert_eq!(sum_dynamic(&values, 5), expected);
+    for _ in 0..2 {
+        assert_eq!(sum_cached(&values, 5), expected);
+    }
+    assert_eq!(sum_fallback_only(&values, 5), expected);
+}
+
+#[test]
+fn dispatch_fn_unit() {
+    let mut value = 1;
+    no_return_value(&mut value);
+    no_return_value(&mut value);
+    assert_eq!(value, 3);
+}
+
+#[test]
+fn dispatch_fn_inner_items() {
+    assert_eq!(with_inner_items(3), 8);
+}
@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+#![allow(unsafe_code)]
+
+use target_feature_dispatch::dispatch_static;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn sum_avx2(values: &[u32], init: u32) -> u32 {
+    values.iter().fold(init, |a, b| a.wrapping_add(*b))
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn sum_sse2(values: &[u32], init: u32) -> u32 {
+    values.iter().fold(init, |a, b| a.wrapping_add(*b))
+}
+
+fn sum_naive(values: &[u32], init: u32) -> u32 {
+    values.iter().fold(init, |a, b| a.wrapping_add(*b))
+}
+
+fn select_name_dynamic() -> &'static str {
+    target_feature_dispatch::target_feature_dispatch! {
+        #[dynamic]
+        if family("x86") {
+            if "avx2" {
+                "x86+AVX2"
+            } else if "sse2" {
+                "x86+SSE2"
+            }
+        } else {
+            "others"
+        }
+    }
+}
+
+dispatch_static! {
+    /// Dynamic dispatching.
+    fn sum_dynamic(values: &[u32], init: u32) -> u32 =
+        #[dynamic]
+        if family("x86") {
+            if "avx2" {
+                |values, init| unsafe { sum_avx2(values, init) }
+            } else if "sse2" {
+                |values, init| unsafe { sum_sse2(values, init) }
+            }
+        } else {
+            sum_naive
+        };
+
+    /// Static dispatching.
+    pub(crate) fn sum_static(values: &[u32], init: u32) -> u32 =
+        if family("x86") {
+            if "avx2" {
+                |values, init| unsafe { sum_avx2(values, init) }
+            } else if "sse2" {
+                |values, init| unsafe { sum_sse2(values, init) }
+            }
+        } else {
+            sum_naive
+        };
+
+    fn name_dynamic() -> &'static str =
+        #[dynamic]
+        if family("x86") {
+            if "avx2" {
+                || "x86+AVX2"
+            } else if "sse2" {
+                || "x86+SSE2"
+            }
+        } else {
+            || "others"
+        };
+
+    fn name_fallback_only() -> &'static str =
+        #[dynamic]
+        #[cfg_non_fallback(any())]
+        if family("x86") {
+            if "avx2" {
+                || "x86+AVX2"
+            }
+        } else {
+            || "others"
+        };
+}
+
+#[test]
+fn dispatch_static_results() {
+    let values: Vec<u32> = (0..1000).map(|x| x * 7 + 3).collect();
+    let expected = sum_naive(&values, 5);
+    // The first call runs the dispatching and later calls use the cached index.
+    for _ in 0..4 {
+        assert_eq!(sum_dynamic(&values, 5), expected);
+        assert_eq!(sum_static(&values, 5), expected);
+    }
+}
+
+#[test]
+fn dispatch_static_selection() {
+    let expected = select_name_dynamic();
+    for _ in 0..4 {
+        assert_eq!(name_dynamic(), expected);
+        assert_eq!(name_fallback_only(), "others");
+    }
+}
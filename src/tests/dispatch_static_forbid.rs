@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+// Dynamic dispatching of `dispatch_static!` generates no unsafe code.
+#![forbid(unsafe_code)]
+
+use target_feature_dispatch::dispatch_static;
+
+fn name_avx2() -> &'static str {
+    "x86+AVX2"
+}
+
+fn name_others() -> &'static str {
+    "others"
+}
+
+dispatch_static! {
+    fn name_dynamic() -> &'static str =
+        #[dynamic]
+        if family("x86") {
+            if "avx2" {
+                name_avx2
+            }
+        } else {
+            name_others
+        };
+}
+
+#[test]
+fn dispatch_static_without_unsafe() {
+    let expected = target_feature_dispatch::target_feature_dispatch! {
+        #[dynamic]
+        if family("x86") {
+            if "avx2" {
+                "x86+AVX2"
+            }
+        } else {
+            "others"
+        }
+    };
+    for _ in 0..4 {
+        assert_eq!(name_dynamic(), expected);
+    }
+}
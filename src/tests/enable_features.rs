@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+#![allow(unsafe_code)]
+
+use target_feature_dispatch::target_feature_dispatch;
+
+fn select_dynamic(values: &[u32]) -> u32 {
+    target_feature_dispatch! {
+        #[dynamic]
+        if family("x86") {
+            if "avx2" && "fma" {
+                values.iter().sum::<u32>() + 300
+            } else if level("x86-64-v2") {
+                values.iter().sum::<u32>() + 200
+            } else if !"sse2" || "sse" {
+                values.iter().sum::<u32>() + 100
+            }
+        } else if family("aarch64") {
+            if "sve" {
+                values.iter().sum::<u32>() + 100
+            }
+        } else {
+            values.iter().sum()
+        }
+    }
+}
+
+#[test]
+fn enable_features() {
+    let values = [1, 2, 3];
+    // Bodies with local variables (captured by the closure).
+    let mut calls = 0;
+    for _ in 0..2 {
+        let value = target_feature_dispatch! {
+            #[cached]
+            #[enable_features]
+            if family("x86") {
+                if "avx2" && "fma" {
+                    calls += 1;
+                    values.iter().sum::<u32>() + 300
+                } else if level("x86-64-v2") {
+                    calls += 1;
+                    values.iter().sum::<u32>() + 200
+                } else if !"sse2" || "sse" {
+                    calls += 1;
+                    values.iter().sum::<u32>() + 100
+                }
+            } else if family("aarch64") {
+                if "sve" {
+                    calls += 1;
+                    values.iter().sum::<u32>() + 100
+                }
+            } else {
+                calls += 1;
+                values.iter().sum()
+            }
+        };
+        assert_eq!(value, select_dynamic(&values));
+    }
+    assert_eq!(calls, 2);
+    // Static dispatching and the conditional form.
+    let value = target_feature_dispatch! {
+        #[cfg_attr(all(), enable_features)]
+        if family("x86") {
+            if "sse2" {
+                values.iter().sum::<u32>() + 100
+            }
+        } else if family("aarch64") {
+            if "neon" {
+                values.iter().sum::<u32>() + 100
+            }
+        } else {
+            values.iter().sum()
+        }
+    };
+    let is_x86_or_aarch64 = cfg!(any(
+        all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            target_feature = "sse2"
+        ),
+        all(
+            any(target_arch = "aarch64", target_arch = "arm64ec"),
+            target_feature = "neon"
+        )
+    ));
+    assert_eq!(value, if is_x86_or_aarch64 { 106 } else { 6 });
+}
+
+#[test]
+fn enable_features_with_loops() {
+    let values = [1, 2, 0, 3];
+    // `break` and `continue` inside loops in the body are allowed.
+    let value = target_feature_dispatch! {
+        #[dynamic]
+        #[enable_features]
+        if family("x86") {
+            if "sse2" {
+                let mut sum = 0;
+                for &x in values.iter() {
+                    if x == 2 {
+                        continue;
+                    }
+                    if x == 0 {
+                        break;
+                    }
+                    sum += x;
+                }
+                sum + 100
+            }
+        } else {
+            values.iter().take_while(|&&x| x != 0).filter(|&&x| x != 2).sum::<i32>()
+        }
+    };
+    let is_x86 = cfg!(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ));
+    assert_eq!(value, if is_x86 { 101 } else { 1 });
+}
+
+#[test]
+fn enable_features_with_nested_closures() {
+    let values = ["1", "2", "x"];
+    // `?` and `return` inside nested closures are allowed.
+    let value = target_feature_dispatch! {
+        #[dynamic]
+        #[enable_features]
+        if family("x86") {
+            if "sse2" {
+                let parse = |s: &str| -> Option<u32> {
+                    if s.is_empty() {
+                        return None;
+                    }
+                    Some(s.parse::<u32>().ok()? + 100)
+                };
+                values.iter().filter_map(|s| parse(s)).sum::<u32>()
+            }
+        } else {
+            values.iter().filter_map(|s| s.parse::<u32>().ok()).sum::<u32>()
+        }
+    };
+    let is_x86 = cfg!(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ));
+    assert_eq!(value, if is_x86 { 203 } else { 3 });
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+#[cfg(feature = "env-ceiling")]
+use target_feature_dispatch::ceiling;
+#[cfg(all(feature = "env-ceiling", feature = "mask"))]
+use target_feature_dispatch::mask;
+use target_feature_dispatch::target_feature_dispatch;
+
+fn select(cached: bool) -> u32 {
+    if cached {
+        target_feature_dispatch! {
+            #[cached]
+            if family("x86") {
+                if "avx2" {
+                    2
+                } else if "sse2" {
+                    1
+                } else {
+                    0
+                }
+            } else if family("aarch64") {
+                if "sve" {
+                    2
+                } else {
+                    0
+                }
+            } else {
+                0
+            }
+        }
+    } else {
+        target_feature_dispatch! {
+            #[dynamic]
+            if family("x86") {
+                if "avx2" {
+                    2
+                } else if "sse2" {
+                    1
+                } else {
+                    0
+                }
+            } else if family("aarch64") {
+                if "sve" {
+                    2
+                } else {
+                    0
+                }
+            } else {
+                0
+            }
+        }
+    }
+}
+
+// All checks are in one test because the variable is read only once.
+#[cfg(feature = "env-ceiling")]
+#[test]
+fn env_disabled_features() {
+    let dynamic = select(false);
+    // SAFETY: No other threads are running in this test binary
+    // (and this is the only test which reads the environment).
+    unsafe {
+        std::env::set_var(ceiling::DISABLE_VAR, " avx2 ,, sve,unknown ");
+    }
+    assert_ne!(select(true), 2);
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(select(true), 1);
+    // Uncached dynamic dispatching is not affected.
+    assert_eq!(select(false), dynamic);
+    // Only the first read is effective.
+    // SAFETY: See above.
+    unsafe {
+        std::env::remove_var(ceiling::DISABLE_VAR);
+    }
+    assert_ne!(select(true), 2);
+    // Masks on the current thread are added to the ceiling
+    // (on filling the cache while the cache is bypassed).
+    #[cfg(all(feature = "mask", target_arch = "x86_64"))]
+    mask::with_disabled(&["sse2"], || assert_eq!(select(true), 0));
+}
+
+// Without the `env-ceiling` feature, the variable is never read.
+#[cfg(not(feature = "env-ceiling"))]
+#[test]
+fn env_ignored_without_env_ceiling() {
+    let expected = [select(false), select(true)];
+    // SAFETY: No other threads are running in this test binary
+    // (and this is the only test which reads the environment).
+    unsafe {
+        std::env::set_var("TARGET_FEATURE_DISPATCH_DISABLE", "avx2,sse2,sve");
+    }
+    assert_eq!([select(false), select(true)], expected);
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    assert_ne!(expected, [0, 0]);
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+use target_feature_dispatch::{define_feature_set, target_feature_dispatch};
+
+define_feature_set!(
+    /// Features introduced by Intel Haswell.
+    pub HASWELL = "avx2" && "fma" && "bmi2"
+);
+define_feature_set!(CRYPTO = "neon" && "aes" && "sha2");
+
+mod features {
+    use target_feature_dispatch::define_feature_set;
+
+    define_feature_set!(pub(crate) SSE4_OR_AVX = ("sse4.1" && "popcnt") || "avx");
+}
+
+mod inner {
+    use target_feature_dispatch::target_feature_dispatch;
+
+    use crate::features::SSE4_OR_AVX;
+
+    pub fn select(dynamic: bool) -> u32 {
+        if dynamic {
+            target_feature_dispatch! {
+                #[dynamic]
+                if family("x86") {
+                    if !set(SSE4_OR_AVX) {
+                        1
+                    } else {
+                        2
+                    }
+                } else {
+                    0
+                }
+            }
+        } else {
+            target_feature_dispatch! {
+                if family("x86") {
+                    if !set(SSE4_OR_AVX) {
+                        1
+                    } else {
+                        2
+                    }
+                } else {
+                    0
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn static_feature_sets() {
+    // Feature sets are equivalent to inline parenthesized conditions.
+    let value = target_feature_dispatch! {
+        if family("x86") {
+            if set(HASWELL) {
+                2
+            } else if "sse2" {
+                1
+            }
+        } else if family("aarch64") {
+            if set(CRYPTO) {
+                2
+            } else if "neon" {
+                1
+            }
+        } else {
+            0
+        }
+    };
+    let expected = target_feature_dispatch! {
+        if family("x86") {
+            if "avx2" && "fma" && "bmi2" {
+                2
+            } else if "sse2" {
+                1
+            }
+        } else if family("aarch64") {
+            if "neon" && "aes" && "sha2" {
+                2
+            } else if "neon" {
+                1
+            }
+        } else {
+            0
+        }
+    };
+    assert_eq!(value, expected);
+    let expected = if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        if cfg!(not(any(
+            all(target_feature = "sse4.1", target_feature = "popcnt"),
+            target_feature = "avx"
+        ))) {
+            1
+        } else {
+            2
+        }
+    } else {
+        0
+    };
+    assert_eq!(inner::select(false), expected);
+}
+
+#[test]
+fn dynamic_feature_sets() {
+    let value = target_feature_dispatch! {
+        #[dynamic]
+        if family("x86") {
+            if set(HASWELL) {
+                2
+            } else if "sse2" {
+                1
+            }
+        } else if family("aarch64") {
+            if set(CRYPTO) {
+                2
+            } else if "neon" {
+                1
+            }
+        } else {
+            0
+        }
+    };
+    let expected = target_feature_dispatch! {
+        #[dynamic]
+        if family("x86") {
+            if "avx2" && "fma" && "bmi2" {
+                2
+            } else if "sse2" {
+                1
+            }
+        } else if family("aarch64") {
+            if "neon" && "aes" && "sha2" {
+                2
+            } else if "neon" {
+                1
+            }
+        } else {
+            0
+        }
+    };
+    assert_eq!(value, expected);
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        use std::arch::is_x86_feature_detected;
+        let sse4_or_avx = is_x86_feature_detected!("sse4.1") && is_x86_feature_detected!("popcnt")
+            || is_x86_feature_detected!("avx");
+        assert_eq!(inner::select(true), if sse4_or_avx { 2 } else { 1 });
+    }
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+use target_feature_dispatch::target_feature_dispatch;
+
+fn selected_static() -> &'static str {
+    target_feature_dispatch! {
+        #[selected_label]
+        if family("x86") {
+            #[label = "AVX2"]
+            if "avx2" {
+                unreachable!()
+            } else #[label = "SSE2"] if "sse2" {
+                unreachable!()
+            }
+        } else {
+            unreachable!()
+        }
+    }
+}
+
+fn selected_dynamic() -> &'static str {
+    target_feature_dispatch! {
+        #[dynamic]
+        #[selected_label]
+        if family("x86") {
+            #[label = "AVX2"]
+            if "avx2" {
+                unreachable!()
+            } else if "sse4.1" && "popcnt" {
+                unreachable!()
+            } else #[label = "SSE2"] if "sse2" {
+                unreachable!()
+            }
+        } else if family("aarch64") {
+            if "sve" {
+                unreachable!()
+            } else {
+                unreachable!()
+            }
+        } else {
+            unreachable!()
+        }
+    }
+}
+
+fn selected_cached() -> &'static str {
+    target_feature_dispatch! {
+        #[cached]
+        #[selected_label]
+        if family("x86") {
+            #[label = "AVX2"]
+            if "avx2" {
+                unreachable!()
+            } else #[label = "SSE2"] if "sse2" {
+                unreachable!()
+            }
+        } else {
+            unreachable!()
+        }
+    }
+}
+
+#[test]
+fn selected_label_static() {
+    let label = selected_static();
+    if cfg!(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "avx2"
+    )) {
+        assert_eq!(label, "AVX2");
+    } else if cfg!(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    )) {
+        assert_eq!(label, "SSE2");
+    } else {
+        assert_eq!(label, "fallback");
+    }
+}
+
+#[test]
+fn selected_label_dynamic() {
+    let label = selected_dynamic();
+    #[cfg(target_arch = "x86_64")]
+    {
+        use std::arch::is_x86_feature_detected;
+        if is_x86_feature_detected!("avx2") {
+            assert_eq!(label, "AVX2");
+        } else if is_x86_feature_detected!("sse4.1") && is_x86_feature_detected!("popcnt") {
+            // Default label: the condition as written.
+            assert_eq!(label, "\"sse4.1\" && \"popcnt\"");
+        } else {
+            assert_eq!(label, "SSE2");
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    assert_eq!(
+        label,
+        if std::arch::is_aarch64_feature_detected!("sve") {
+            // Default label: the feature itself.
+            "sve"
+        } else {
+            "fallback"
+        }
+    );
+    let _ = label;
+    assert_eq!(selected_cached(), selected_cached());
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(
+        selected_cached() == "AVX2",
+        std::arch::is_x86_feature_detected!("avx2")
+    );
+}
+
+#[test]
+fn labels_with_cfg() {
+    let label = target_feature_dispatch! {
+        #[dynamic]
+        #[selected_label]
+        if family("x86") {
+            #[cfg(any())]
+            #[label = "removed"]
+            if "avx2" {
+                unreachable!()
+            } else #[label = "SSE2"] #[cfg(all())] if "sse2" {
+                unreachable!()
+            } else {
+                unreachable!()
+            }
+        } else {
+            unreachable!()
+        }
+    };
+    assert_ne!(label, "removed");
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(label, "SSE2");
+}
+
+#[test]
+fn labels_on_all_branches() {
+    let labels: Vec<&str> = target_feature_dispatch! {
+        #[all_branches]
+        if family("x86") {
+            #[label = "AVX2"]
+            if "avx2" {
+                2
+            } else #[label = "SSE2"] if "sse2" {
+                1
+            }
+        } else {
+            0
+        }
+    }
+    .map(|(label, _)| label)
+    .collect();
+    assert_eq!(labels.last(), Some(&"fallback"));
+    #[cfg(target_arch = "x86_64")]
+    {
+        assert!(labels.contains(&"SSE2"));
+        assert_eq!(
+            labels.contains(&"AVX2"),
+            std::arch::is_x86_feature_detected!("avx2")
+        );
+    }
+}
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+#![cfg(feature = "mask")]
+
+use target_feature_dispatch::{dispatch_static, mask, target_feature_dispatch};
+
+fn select_dynamic() -> u32 {
+    target_feature_dispatch! {
+        #[dynamic]
+        if family("x86") {
+            if "avx2" {
+                3
+            } else if "avx" {
+                2
+            } else if "sse2" {
+                1
+            } else {
+                0
+            }
+        } else if family("aarch64") {
+            if "sve" {
+                3
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    }
+}
+
+fn select_cached() -> u32 {
+    target_feature_dispatch! {
+        #[dynamic]
+        #[cached]
+        if family("x86") {
+            if "avx2" {
+                3
+            } else if "avx" {
+                2
+            } else if "sse2" {
+                1
+            } else {
+                0
+            }
+        } else if family("aarch64") {
+            if "sve" {
+                3
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    }
+}
+
+dispatch_static! {
+    fn select_static() -> u32 =
+        #[dynamic]
+        if family("x86") {
+            if "avx2" {
+                fn_avx2
+            } else {
+                fn_others
+            }
+        } else {
+            fn_others
+        };
+}
+
+fn fn_avx2() -> u32 {
+    3
+}
+
+fn fn_others() -> u32 {
+    0
+}
+
+#[test]
+fn masked_features() {
+    for select in [select_dynamic, select_cached] {
+        let unmasked = select();
+        mask::with_disabled(&["avx2", "sve"], || {
+            assert_ne!(select(), 3);
+            mask::with_disabled(&["avx"], || {
+                assert!(select() <= 1);
+            });
+            // The inner mask is removed.
+            assert_eq!(
+                select(),
+                if unmasked == 3 {
+                    select_avx()
+                } else {
+                    unmasked
+                }
+            );
+        });
+        // All masks are removed.
+        assert_eq!(select(), unmasked);
+    }
+}
+
+/// Selection without AVX2 (on x86).
+fn select_avx() -> u32 {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    if std::arch::is_x86_feature_detected!("avx") {
+        return 2;
+    }
+    if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        1
+    } else {
+        0
+    }
+}
+
+#[test]
+fn masked_dispatch_static() {
+    let unmasked = select_static();
+    mask::with_disabled(&["avx2"], || {
+        assert_eq!(select_static(), 0);
+    });
+    assert_eq!(select_static(), unmasked);
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(unmasked == 3, std::arch::is_x86_feature_detected!("avx2"));
+}
+
+#[test]
+fn masked_only_on_current_thread() {
+    let unmasked = select_dynamic();
+    mask::with_disabled(&["avx2", "avx", "sse2", "sve"], || {
+        assert_eq!(select_dynamic(), 0);
+        let other = std::thread::spawn(select_dynamic).join().unwrap();
+        assert_eq!(other, unmasked);
+    });
+}
+
+#[test]
+fn mask_restored_on_panic() {
+    let unmasked = select_dynamic();
+    let result = std::panic::catch_unwind(|| {
+        mask::with_disabled(&["avx2", "avx", "sse2", "sve"], || panic!("masked"));
+    });
+    assert!(result.is_err());
+    assert_eq!(select_dynamic(), unmasked);
+}
@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+#![cfg(feature = "proof-tokens")]
+#![allow(unsafe_code)]
+
+use target_feature_dispatch::target_feature_dispatch;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod kernels {
+    use target_feature_dispatch::tokens::{Avx2Token, FmaToken, Sse2Token};
+
+    #[target_feature(enable = "avx2")]
+    fn sum_avx2(values: &[u32]) -> u32 {
+        values.iter().sum()
+    }
+
+    // Safe API accepting a proof token.
+    pub fn sum_with_avx2(_token: Avx2Token, values: &[u32]) -> u32 {
+        // SAFETY: The token proves that AVX2 is available.
+        unsafe { sum_avx2(values) }
+    }
+
+    pub fn uses_fma(_tokens: (Avx2Token, FmaToken)) -> u32 {
+        2
+    }
+
+    pub fn uses_sse2(_token: Sse2Token) -> u32 {
+        1
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[test]
+fn proof_tokens() {
+    use std::arch::is_x86_feature_detected;
+    assert_eq!(size_of::<target_feature_dispatch::tokens::Avx2Token>(), 0);
+    let values = [1, 2, 3];
+    let value = target_feature_dispatch! {
+        #[dynamic]
+        if family("x86") {
+            if "avx2" as token {
+                kernels::sum_with_avx2(token, &values)
+            } else {
+                0
+            }
+        } else {
+            unreachable!()
+        }
+    };
+    let expected = if is_x86_feature_detected!("avx2") {
+        6
+    } else {
+        0
+    };
+    assert_eq!(value, expected);
+    // Multiple features (a tuple) on static, dynamic and cached dispatching.
+    fn select(mode: u32) -> u32 {
+        match mode {
+            0 => target_feature_dispatch! {
+                if family("x86") {
+                    if "avx2" && "fma" as tokens {
+                        kernels::uses_fma(tokens)
+                    } else if "sse2" as token {
+                        kernels::uses_sse2(token)
+                    } else {
+                        0
+                    }
+                } else {
+                    unreachable!()
+                }
+            },
+            1 => target_feature_dispatch! {
+                #[dynamic]
+                if family("x86") {
+                    if "avx2" && "fma" as tokens {
+                        kernels::uses_fma(tokens)
+                    } else if "sse2" as token {
+                        kernels::uses_sse2(token)
+                    } else {
+                        0
+                    }
+                } else {
+                    unreachable!()
+                }
+            },
+            _ => target_feature_dispatch! {
+                #[cached]
+                if family("x86") {
+                    #[cfg(all())]
+                    if "avx2" && "fma" as tokens {
+                        kernels::uses_fma(tokens)
+                    } else if "sse2" as token {
+                        kernels::uses_sse2(token)
+                    } else {
+                        0
+                    }
+                } else {
+                    unreachable!()
+                }
+            },
+        }
+    }
+    let expected = if cfg!(all(target_feature = "avx2", target_feature = "fma")) {
+        2
+    } else if cfg!(target_feature = "sse2") {
+        1
+    } else {
+        0
+    };
+    assert_eq!(select(0), expected);
+    let expected = if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+        2
+    } else if is_x86_feature_detected!("sse2") {
+        1
+    } else {
+        0
+    };
+    assert_eq!(select(1), expected);
+    for _ in 0..2 {
+        assert_eq!(select(2), expected);
+    }
+}
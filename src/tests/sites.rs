@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+#![cfg(all(feature = "sites", any(target_os = "linux", target_os = "android")))]
+// Sites are statics with `#[unsafe(link_section)]` (allowed per static),
+// which work with `deny(unsafe_code)` but not with `forbid(unsafe_code)`.
+#![deny(unsafe_code)]
+
+use target_feature_dispatch::sites::Site;
+use target_feature_dispatch::target_feature_dispatch;
+
+fn select(value: u32) -> u32 {
+    target_feature_dispatch! {
+        #[dynamic]
+        #[site = "tests::sites::select"]
+        if family("x86") {
+            #[label = "AVX2"]
+            if "avx2" {
+                value + 2
+            } else #[label = "SSE2"] if "sse2" {
+                value + 1
+            }
+        } else if family("aarch64") {
+            #[label = "SVE"]
+            if "sve" {
+                value + 2
+            }
+        } else {
+            value
+        }
+    }
+}
+
+fn select_static(value: u32) -> u32 {
+    target_feature_dispatch! {
+        #[site = "tests::sites::select_static"]
+        if family("x86") {
+            #[label = "SSE2"]
+            if "sse2" {
+                value + 1
+            }
+        } else {
+            value
+        }
+    }
+}
+
+fn find(name: &str) -> &'static Site {
+    target_feature_dispatch::sites()
+        .find(|site| site.name() == name)
+        .expect("the site is not registered")
+}
+
+fn labels(site: &Site) -> Vec<&'static str> {
+    let mut labels = Vec::new();
+    site.for_each_label(|label| labels.push(label));
+    labels
+}
+
+#[test]
+fn registered() {
+    // The registration does not depend on executing the invocation.
+    assert_eq!(
+        target_feature_dispatch::sites()
+            .filter(|site| site.name() == "tests::sites::select")
+            .count(),
+        1
+    );
+    find("tests::sites::select_static");
+}
+
+#[test]
+fn selected_label_dynamic() {
+    let site = find("tests::sites::select");
+    let labels = labels(site);
+    assert_eq!(labels.last(), Some(&"fallback"));
+    assert_eq!(site.selected(), labels[0]);
+    let expected = match site.selected() {
+        "AVX2" | "SVE" => 3,
+        "SSE2" => 2,
+        _ => 1,
+    };
+    assert_eq!(select(1), expected);
+}
+
+#[test]
+fn selected_label_static() {
+    let site = find("tests::sites::select_static");
+    if cfg!(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    )) {
+        assert_eq!(site.selected(), "SSE2");
+        assert_eq!(labels(site), ["SSE2", "fallback"]);
+        assert_eq!(select_static(1), 2);
+    } else {
+        assert_eq!(site.selected(), "fallback");
+        assert_eq!(labels(site), ["fallback"]);
+        assert_eq!(select_static(1), 1);
+    }
+}
+
+#[test]
+fn all_labels() {
+    // `all_branches` and `selected_label` (labels of supported branches).
+    let labels: Vec<&'static str> = target_feature_dispatch! {
+        #[all_branches]
+        #[selected_label]
+        if family("x86") {
+            #[label = "SSE2"]
+            if "sse2" {
+                unreachable!()
+            }
+        } else {
+            unreachable!()
+        }
+    }
+    .collect();
+    assert_eq!(labels.last(), Some(&"fallback"));
+    assert!(labels.len() <= 2);
+}
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+// `verify_static` is only supported on x86.
+#![cfg(all(
+    feature = "verify-static",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+
+use target_feature_dispatch::target_feature_dispatch;
+
+fn select_static() -> u32 {
+    target_feature_dispatch! {
+        #[verify_static]
+        if family("x86") {
+            if "avx2" && "fma" {
+                3
+            } else if ("sse4.1" || "sse4.2") && !"avx512f" {
+                2
+            } else if "sse2" {
+                1
+            } else {
+                0
+            }
+        } else if family("aarch64") {
+            if "sve" {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    }
+}
+
+fn select_dynamic() -> u32 {
+    target_feature_dispatch! {
+        #[dynamic]
+        #[verify_static]
+        if family("x86") {
+            if "avx2" {
+                2
+            } else if "sse2" {
+                1
+            }
+        } else {
+            0
+        }
+    }
+}
+
+// Conditionally disabled verification can be used in the constant context.
+const _SELECTED: u32 = target_feature_dispatch! {
+    #[cfg_attr(any(), verify_static)]
+    if family("x86") {
+        if "sse2" {
+            1
+        }
+    } else {
+        0
+    }
+};
+
+#[test]
+fn verified() {
+    // The features enabled on compilation are available on the host.
+    select_static();
+    select_dynamic();
+}
+
+#[test]
+fn consistent_with_std() {
+    use target_feature_dispatch::__private::cpuid::is_available;
+    macro_rules! check {
+        ($($feat: tt),+) => {
+            $(
+                assert_eq!(
+                    is_available($feat),
+                    Some(std::arch::is_x86_feature_detected!($feat)),
+                    "feature: {}", $feat
+                );
+            )+
+        };
+    }
+    check!(
+        "sse",
+        "sse2",
+        "sse3",
+        "ssse3",
+        "sse4.1",
+        "sse4.2",
+        "popcnt",
+        "avx",
+        "avx2",
+        "fma",
+        "f16c",
+        "bmi1",
+        "bmi2",
+        "lzcnt",
+        "aes",
+        "pclmulqdq",
+        "sha",
+        "avx512f",
+        "avx512bw",
+        "avx512vl",
+        "xsave",
+        "xsaveopt"
+    );
+    assert_eq!(is_available("unknown"), None);
+}
@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: MIT
+// SPDX-FileCopyrightText: Copyright (C) 2025 Tsukasa OI <floss_rust@irq.a4lg.com>.
+
+use target_feature_dispatch::target_feature_dispatch;
+
+fn select_static() -> u32 {
+    target_feature_dispatch! {
+        if family("riscv") {
+            if vlen(>= 256) {
+                3
+            } else if vlen(>= 128) && "zvbb" {
+                2
+            } else if "v" {
+                1
+            } else {
+                0
+            }
+        } else if family("aarch64") {
+            if sve_vl(>= 256) {
+                2
+            } else if "sve" {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+#[test]
+fn static_mapping() {
+    let expected = if cfg!(all(target_feature = "v", target_feature = "zvl256b")) {
+        3
+    } else if cfg!(all(
+        target_feature = "v",
+        target_feature = "zvl128b",
+        target_feature = "zvbb"
+    )) {
+        2
+    } else if cfg!(target_feature = "v") {
+        1
+    } else {
+        0
+    };
+    assert_eq!(select_static(), expected);
+}
+
+#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+#[test]
+fn static_mapping() {
+    let expected = if cfg!(all(target_arch = "aarch64", target_feature = "sve")) {
+        // `sve_vl` is never satisfied on static dispatching.
+        1
+    } else {
+        0
+    };
+    assert_eq!(select_static(), expected);
+}
+
+#[cfg(feature = "vlen")]
+fn select_dynamic() -> u32 {
+    target_feature_dispatch! {
+        #[dynamic]
+        if family("riscv") {
+            if vlen(>= 256) {
+                2
+            } else if "v" {
+                1
+            } else {
+                0
+            }
+        } else if family("aarch64") {
+            if sve_vl(>= 256) {
+                2
+            } else if "sve" {
+                1
+            } else {
+                0
+            }
+        } else if family("x86") {
+            if "avx2" {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(feature = "vlen")]
+#[test]
+fn other_architectures_unaffected() {
+    let value = select_dynamic();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    assert_eq!(
+        value,
+        if std::arch::is_x86_feature_detected!("avx2") {
+            1
+        } else {
+            0
+        }
+    );
+    let _ = value;
+}
+
+#[cfg(all(feature = "vlen", feature = "std", target_arch = "aarch64"))]
+#[test]
+fn consistent_with_sve_vl() {
+    let expected = if std::arch::is_aarch64_feature_detected!("sve") {
+        // SAFETY: SVE is detected.
+        if unsafe { target_feature_dispatch::__private::vlen::sve_vl() } >= 256 {
+            2
+        } else {
+            1
+        }
+    } else {
+        0
+    };
+    assert_eq!(select_dynamic(), expected);
+}
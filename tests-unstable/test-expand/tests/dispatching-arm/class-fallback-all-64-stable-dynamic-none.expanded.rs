@@ -9,7 +9,30 @@ fn sample() {
                         target_arch = "arm64ec",
                         target_arch = "arm"
                     )
-                )] { { #[cfg(not(all(target_feature = "dotprod")))] { "fallback" } } }
+                )]
+                {
+                    {
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "class(\"arm\")",
+                            &[
+                                &[
+                                    ::target_feature_dispatch::__private::features::ARM,
+                                    ::target_feature_dispatch::__private::features::AARCH64,
+                                ],
+                            ],
+                            "dotprod",
+                        );
+                        {
+                            #[cfg(not(any()))]
+                            {
+                                {
+                                    #[cfg(not(all(target_feature = "dotprod")))]
+                                    { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
     };
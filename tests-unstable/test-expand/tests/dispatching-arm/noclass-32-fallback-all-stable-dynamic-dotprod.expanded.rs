@@ -6,17 +6,33 @@ fn sample() {
                 #[cfg(any(target_arch = "arm"))]
                 {
                     {
-                        #[cfg(
-                            not(
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"arm\"",
+                            &[&[::target_feature_dispatch::__private::features::ARM]],
+                            "dotprod",
+                        );
+                        {
+                            #[cfg(
                                 all(
-                                    any(any(), feature = "detect-features"),
-                                    any(any(), feature = "unstable")
+                                    not(
+                                        all(
+                                            any(any(), feature = "detect-features"),
+                                            any(any(), feature = "unstable")
+                                        )
+                                    ),
+                                    not(any())
                                 )
-                            )
-                        )]
-                        {
+                            )]
                             {
-                                #[cfg(all(target_feature = "dotprod"))] { "Arm + DOTPROD" }
+                                {
+                                    #[cfg(all(target_feature = "dotprod"))]
+                                    {
+                                        {
+                                            #[cfg(not(any(any(), any())))]
+                                            { { #[cfg(not(any()))] { "Arm + DOTPROD" } } }
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -7,21 +7,39 @@ fn sample() {
                 #[cfg(any(target_arch = "arm"))]
                 {
                     {
-                        #[cfg(
-                            all(
-                                any(any(), feature = "detect-features"),
-                                any(any(), feature = "unstable")
-                            )
-                        )]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"arm\"",
+                            &[&[::target_feature_dispatch::__private::features::ARM]],
+                            "dotprod",
+                        );
                         {
-                            if {
-                                true
-                                    || ::std_detect::detect::__is_feature_detected::dotprod()
-                            } {
-                                "Arm + DOTPROD"
-                            } else {
-                                {
-                                    #[cfg(all(target_feature = "dotprod"))] { "Arm + DOTPROD" }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    any(any(), feature = "unstable"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    true
+                                        || ::std_detect::detect::__is_feature_detected::dotprod()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "Arm + DOTPROD" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(all(target_feature = "dotprod"))]
+                                        {
+                                            {
+                                                #[cfg(not(any(any(), any())))]
+                                                { { #[cfg(not(any()))] { "Arm + DOTPROD" } } }
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
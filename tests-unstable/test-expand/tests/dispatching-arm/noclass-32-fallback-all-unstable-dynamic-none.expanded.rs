@@ -7,21 +7,34 @@ fn sample() {
                 #[cfg(any(target_arch = "arm"))]
                 {
                     {
-                        #[cfg(
-                            all(
-                                any(any(), feature = "detect-features"),
-                                any(any(), feature = "unstable")
-                            )
-                        )]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"arm\"",
+                            &[&[::target_feature_dispatch::__private::features::ARM]],
+                            "dotprod",
+                        );
                         {
-                            if {
-                                false
-                                    || ::std_detect::detect::__is_feature_detected::dotprod()
-                            } {
-                                "Arm + DOTPROD"
-                            } else {
-                                {
-                                    #[cfg(not(all(target_feature = "dotprod")))] { "fallback" }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    any(any(), feature = "unstable"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    false
+                                        || ::std_detect::detect::__is_feature_detected::dotprod()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "Arm + DOTPROD" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(not(all(target_feature = "dotprod")))]
+                                        { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                    }
                                 }
                             }
                         }
@@ -7,17 +7,28 @@ fn sample() {
                 #[cfg(any(target_arch = "arm"))]
                 {
                     {
-                        #[cfg(
-                            not(
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"arm\"",
+                            &[&[::target_feature_dispatch::__private::features::ARM]],
+                            "dotprod",
+                        );
+                        {
+                            #[cfg(
                                 all(
-                                    any(any(), feature = "detect-features"),
-                                    any(any(), feature = "unstable")
+                                    not(
+                                        all(
+                                            any(any(), feature = "detect-features"),
+                                            any(any(), feature = "unstable")
+                                        )
+                                    ),
+                                    not(any())
                                 )
-                            )
-                        )]
-                        {
+                            )]
                             {
-                                #[cfg(not(all(target_feature = "dotprod")))] { "fallback" }
+                                {
+                                    #[cfg(not(all(target_feature = "dotprod")))]
+                                    { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                }
                             }
                         }
                     }
@@ -6,16 +6,35 @@ fn sample() {
                 #[cfg(any(target_arch = "aarch64"))]
                 {
                     {
-                        #[cfg(any(any(), feature = "detect-features"))]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"aarch64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::AARCH64],
+                            ],
+                            "dotprod",
+                        );
                         {
-                            if {
-                                false
-                                    || ::std_detect::detect::__is_feature_detected::dotprod()
-                            } {
-                                "Arm + DOTPROD"
-                            } else {
-                                {
-                                    #[cfg(not(all(target_feature = "dotprod")))] { "fallback" }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    false
+                                        || ::std_detect::detect::__is_feature_detected::dotprod()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "Arm + DOTPROD" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(not(all(target_feature = "dotprod")))]
+                                        { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                    }
                                 }
                             }
                         }
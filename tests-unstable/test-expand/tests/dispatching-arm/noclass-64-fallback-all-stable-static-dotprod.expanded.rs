@@ -6,10 +6,30 @@ fn sample() {
                 #[cfg(any(target_arch = "aarch64"))]
                 {
                     {
-                        #[cfg(not(any(any(), feature = "detect-features")))]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"aarch64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::AARCH64],
+                            ],
+                            "dotprod",
+                        );
                         {
+                            #[cfg(
+                                all(
+                                    not(any(any(), feature = "detect-features")),
+                                    not(any())
+                                )
+                            )]
                             {
-                                #[cfg(all(target_feature = "dotprod"))] { "Arm + DOTPROD" }
+                                {
+                                    #[cfg(all(target_feature = "dotprod"))]
+                                    {
+                                        {
+                                            #[cfg(not(any(any(), any())))]
+                                            { { #[cfg(not(any()))] { "Arm + DOTPROD" } } }
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -6,10 +6,25 @@ fn sample() {
                 #[cfg(any(target_arch = "aarch64"))]
                 {
                     {
-                        #[cfg(not(any(any(), feature = "detect-features")))]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"aarch64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::AARCH64],
+                            ],
+                            "dotprod",
+                        );
                         {
+                            #[cfg(
+                                all(
+                                    not(any(any(), feature = "detect-features")),
+                                    not(any())
+                                )
+                            )]
                             {
-                                #[cfg(not(all(target_feature = "dotprod")))] { "fallback" }
+                                {
+                                    #[cfg(not(all(target_feature = "dotprod")))]
+                                    { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                }
                             }
                         }
                     }
@@ -6,15 +6,32 @@ fn sample() {
                 #[cfg(any(target_arch = "loongarch64"))]
                 {
                     {
-                        #[cfg(
-                            not(
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"loongarch64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::LOONGARCH],
+                            ],
+                            "lvz",
+                        );
+                        {
+                            #[cfg(
                                 all(
-                                    any(any(), feature = "detect-features"),
-                                    any(any(), feature = "unstable")
+                                    not(
+                                        all(
+                                            any(any(), feature = "detect-features"),
+                                            any(any(), feature = "unstable")
+                                        )
+                                    ),
+                                    not(any())
                                 )
-                            )
-                        )]
-                        { { #[cfg(not(all(target_feature = "lvz")))] { "fallback" } } }
+                            )]
+                            {
+                                {
+                                    #[cfg(not(all(target_feature = "lvz")))]
+                                    { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -7,20 +7,40 @@ fn sample() {
                 #[cfg(any(target_arch = "loongarch64"))]
                 {
                     {
-                        #[cfg(
-                            all(
-                                any(any(), feature = "detect-features"),
-                                any(any(), feature = "unstable")
-                            )
-                        )]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"loongarch64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::LOONGARCH],
+                            ],
+                            "lvz",
+                        );
                         {
-                            if {
-                                true || ::std_detect::detect::__is_feature_detected::lvz()
-                            } {
-                                "LoongArch64 + LVZ"
-                            } else {
-                                {
-                                    #[cfg(all(target_feature = "lvz"))] { "LoongArch64 + LVZ" }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    any(any(), feature = "unstable"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    true || ::std_detect::detect::__is_feature_detected::lvz()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "LoongArch64 + LVZ" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(all(target_feature = "lvz"))]
+                                        {
+                                            {
+                                                #[cfg(not(any(any(), any())))]
+                                                { { #[cfg(not(any()))] { "LoongArch64 + LVZ" } } }
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
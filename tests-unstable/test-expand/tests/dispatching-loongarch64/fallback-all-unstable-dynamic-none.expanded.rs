@@ -7,19 +7,36 @@ fn sample() {
                 #[cfg(any(target_arch = "loongarch64"))]
                 {
                     {
-                        #[cfg(
-                            all(
-                                any(any(), feature = "detect-features"),
-                                any(any(), feature = "unstable")
-                            )
-                        )]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"loongarch64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::LOONGARCH],
+                            ],
+                            "lvz",
+                        );
                         {
-                            if {
-                                false || ::std_detect::detect::__is_feature_detected::lvz()
-                            } {
-                                "LoongArch64 + LVZ"
-                            } else {
-                                { #[cfg(not(all(target_feature = "lvz")))] { "fallback" } }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    any(any(), feature = "unstable"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    false || ::std_detect::detect::__is_feature_detected::lvz()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "LoongArch64 + LVZ" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(not(all(target_feature = "lvz")))]
+                                        { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                    }
+                                }
                             }
                         }
                     }
@@ -5,7 +5,31 @@ fn sample() {
         {
             {
                 #[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
-                { { #[cfg(all(target_feature = "altivec"))] { "PowerPC + AltiVec" } } }
+                {
+                    {
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "class(\"powerpc\")",
+                            &[
+                                &[::target_feature_dispatch::__private::features::POWERPC],
+                            ],
+                            "altivec",
+                        );
+                        {
+                            #[cfg(not(any()))]
+                            {
+                                {
+                                    #[cfg(all(target_feature = "altivec"))]
+                                    {
+                                        {
+                                            #[cfg(not(any(any(), any())))]
+                                            { { #[cfg(not(any()))] { "PowerPC + AltiVec" } } }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
     };
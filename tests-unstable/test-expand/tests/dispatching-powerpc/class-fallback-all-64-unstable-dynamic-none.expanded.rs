@@ -5,7 +5,26 @@ fn sample() {
         {
             {
                 #[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
-                { { #[cfg(not(all(target_feature = "altivec")))] { "fallback" } } }
+                {
+                    {
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "class(\"powerpc\")",
+                            &[
+                                &[::target_feature_dispatch::__private::features::POWERPC],
+                            ],
+                            "altivec",
+                        );
+                        {
+                            #[cfg(not(any()))]
+                            {
+                                {
+                                    #[cfg(not(all(target_feature = "altivec")))]
+                                    { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
     };
@@ -6,18 +6,35 @@ fn sample() {
                 #[cfg(any(target_arch = "powerpc"))]
                 {
                     {
-                        #[cfg(
-                            not(
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"powerpc\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::POWERPC],
+                            ],
+                            "altivec",
+                        );
+                        {
+                            #[cfg(
                                 all(
-                                    any(any(), feature = "detect-features"),
-                                    any(any(), feature = "unstable")
+                                    not(
+                                        all(
+                                            any(any(), feature = "detect-features"),
+                                            any(any(), feature = "unstable")
+                                        )
+                                    ),
+                                    not(any())
                                 )
-                            )
-                        )]
-                        {
+                            )]
                             {
-                                #[cfg(all(target_feature = "altivec"))]
-                                { "PowerPC + AltiVec" }
+                                {
+                                    #[cfg(all(target_feature = "altivec"))]
+                                    {
+                                        {
+                                            #[cfg(not(any(any(), any())))]
+                                            { { #[cfg(not(any()))] { "PowerPC + AltiVec" } } }
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
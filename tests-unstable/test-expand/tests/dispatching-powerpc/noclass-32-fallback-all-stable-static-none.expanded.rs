@@ -6,17 +6,30 @@ fn sample() {
                 #[cfg(any(target_arch = "powerpc"))]
                 {
                     {
-                        #[cfg(
-                            not(
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"powerpc\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::POWERPC],
+                            ],
+                            "altivec",
+                        );
+                        {
+                            #[cfg(
                                 all(
-                                    any(any(), feature = "detect-features"),
-                                    any(any(), feature = "unstable")
+                                    not(
+                                        all(
+                                            any(any(), feature = "detect-features"),
+                                            any(any(), feature = "unstable")
+                                        )
+                                    ),
+                                    not(any())
                                 )
-                            )
-                        )]
-                        {
+                            )]
                             {
-                                #[cfg(not(all(target_feature = "altivec")))] { "fallback" }
+                                {
+                                    #[cfg(not(all(target_feature = "altivec")))]
+                                    { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                }
                             }
                         }
                     }
@@ -7,22 +7,41 @@ fn sample() {
                 #[cfg(any(target_arch = "powerpc64"))]
                 {
                     {
-                        #[cfg(
-                            all(
-                                any(any(), feature = "detect-features"),
-                                any(any(), feature = "unstable")
-                            )
-                        )]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"powerpc64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::POWERPC],
+                            ],
+                            "altivec",
+                        );
                         {
-                            if {
-                                true
-                                    || ::std_detect::detect::__is_feature_detected::altivec()
-                            } {
-                                "PowerPC + AltiVec"
-                            } else {
-                                {
-                                    #[cfg(all(target_feature = "altivec"))]
-                                    { "PowerPC + AltiVec" }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    any(any(), feature = "unstable"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    true
+                                        || ::std_detect::detect::__is_feature_detected::altivec()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "PowerPC + AltiVec" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(all(target_feature = "altivec"))]
+                                        {
+                                            {
+                                                #[cfg(not(any(any(), any())))]
+                                                { { #[cfg(not(any()))] { "PowerPC + AltiVec" } } }
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
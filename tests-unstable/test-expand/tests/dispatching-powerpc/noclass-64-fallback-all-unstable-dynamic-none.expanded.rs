@@ -7,21 +7,36 @@ fn sample() {
                 #[cfg(any(target_arch = "powerpc64"))]
                 {
                     {
-                        #[cfg(
-                            all(
-                                any(any(), feature = "detect-features"),
-                                any(any(), feature = "unstable")
-                            )
-                        )]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"powerpc64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::POWERPC],
+                            ],
+                            "altivec",
+                        );
                         {
-                            if {
-                                false
-                                    || ::std_detect::detect::__is_feature_detected::altivec()
-                            } {
-                                "PowerPC + AltiVec"
-                            } else {
-                                {
-                                    #[cfg(not(all(target_feature = "altivec")))] { "fallback" }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    any(any(), feature = "unstable"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    false
+                                        || ::std_detect::detect::__is_feature_detected::altivec()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "PowerPC + AltiVec" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(not(all(target_feature = "altivec")))]
+                                        { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                    }
                                 }
                             }
                         }
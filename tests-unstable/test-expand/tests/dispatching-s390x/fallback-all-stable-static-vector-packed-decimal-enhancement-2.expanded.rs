@@ -6,19 +6,40 @@ fn sample() {
                 #[cfg(any(target_arch = "s390x"))]
                 {
                     {
-                        #[cfg(
-                            not(
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"s390x\"",
+                            &[&[::target_feature_dispatch::__private::features::S390X]],
+                            "vector-packed-decimal-enhancement-2",
+                        );
+                        {
+                            #[cfg(
                                 all(
-                                    any(any(), feature = "detect-features"),
-                                    any(any(), feature = "unstable")
+                                    not(
+                                        all(
+                                            any(any(), feature = "detect-features"),
+                                            any(any(), feature = "unstable")
+                                        )
+                                    ),
+                                    not(any())
                                 )
-                            )
-                        )]
-                        {
+                            )]
                             {
-                                #[cfg(
-                                    all(target_feature = "vector-packed-decimal-enhancement-2")
-                                )] { "s390x + vector packed decimal enhancement (2)" }
+                                {
+                                    #[cfg(
+                                        all(target_feature = "vector-packed-decimal-enhancement-2")
+                                    )]
+                                    {
+                                        {
+                                            #[cfg(not(any(any(), any())))]
+                                            {
+                                                {
+                                                    #[cfg(not(any()))]
+                                                    { "s390x + vector packed decimal enhancement (2)" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -7,23 +7,51 @@ fn sample() {
                 #[cfg(any(target_arch = "s390x"))]
                 {
                     {
-                        #[cfg(
-                            all(
-                                any(any(), feature = "detect-features"),
-                                any(any(), feature = "unstable")
-                            )
-                        )]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"s390x\"",
+                            &[&[::target_feature_dispatch::__private::features::S390X]],
+                            "vector-packed-decimal-enhancement-2",
+                        );
                         {
-                            if {
-                                true
-                                    || ::std_detect::detect::__is_feature_detected::vector_packed_decimal_enhancement_2()
-                            } {
-                                "s390x + vector packed decimal enhancement (2)"
-                            } else {
-                                {
-                                    #[cfg(
-                                        all(target_feature = "vector-packed-decimal-enhancement-2")
-                                    )] { "s390x + vector packed decimal enhancement (2)" }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    any(any(), feature = "unstable"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    true
+                                        || ::std_detect::detect::__is_feature_detected::vector_packed_decimal_enhancement_2()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        {
+                                            {
+                                                #[cfg(not(any()))]
+                                                { "s390x + vector packed decimal enhancement (2)" }
+                                            }
+                                        }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(
+                                            all(target_feature = "vector-packed-decimal-enhancement-2")
+                                        )]
+                                        {
+                                            {
+                                                #[cfg(not(any(any(), any())))]
+                                                {
+                                                    {
+                                                        #[cfg(not(any()))]
+                                                        { "s390x + vector packed decimal enhancement (2)" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
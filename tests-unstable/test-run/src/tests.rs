@@ -17,6 +17,7 @@ fn test_arm_features() {
         #[cfg_non_fallback(feature = "dispatch")]
         #[cfg_attr(feature = "detect-features", dynamic)]
         #[cfg_attr(feature = "unstable", unstable)]
+        #[cfg_attr(feature = "detect-auxv", dynamic(auxv))]
         if family("aarch64") {
             if "neon" {
                 Some(true)
@@ -37,6 +38,7 @@ fn test_arm_features() {
         #[cfg_non_fallback(feature = "dispatch")]
         #[cfg_attr(feature = "detect-features", dynamic)]
         #[cfg_attr(feature = "unstable", unstable)]
+        #[cfg_attr(feature = "detect-auxv", dynamic(auxv))]
         if family("aarch64") {
             if "dotprod" {
                 Some(true)
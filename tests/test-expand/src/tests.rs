@@ -233,6 +233,26 @@ fn dispatch_behavior_on_riscv() {
     }
 }
 
+#[test]
+fn profiles_on_riscv() {
+    unsafe {
+        env::set_var("RUSTFLAGS", "");
+    }
+    // Static dispatching only (the standard library of the MSRV cannot
+    // detect some of the mandatory extensions at runtime).
+    for profile in ["rva20u64", "rva22u64", "rva23u64"] {
+        expand_args(
+            format!("tests/dispatching-riscv/profile-{profile}.rs"),
+            &[
+                "--target",
+                "riscv64gc-unknown-linux-musl",
+                "--features",
+                "dispatch",
+            ],
+        );
+    }
+}
+
 #[test]
 fn dispatch_behavior_on_x86() {
     unsafe {
@@ -417,8 +437,17 @@ fn dispatch_behavior_on_x86_additional() {
                         ),
                     );
                 }
-                // Expand and test two files each.
+                // Expand and test three files each.
                 let features = format!("dispatch{suffix}", suffix = dispatch.feature_suffix);
+                expand_args(
+                    format!(
+                        "tests/dispatching-x86/cached-{target}-{dispatch}-{feature}.rs",
+                        target = target.filename,
+                        dispatch = dispatch.filename,
+                        feature = feature.filename
+                    ),
+                    &["--features", features.as_str()],
+                );
                 expand_args(
                     format!(
                         "tests/dispatching-x86/family-fallback-x86-{target}-{dispatch}-{feature}.rs",
@@ -440,4 +469,13 @@ fn dispatch_behavior_on_x86_additional() {
             }
         }
     }
+    // Cached dispatching with masking
+    // (checks and cache bypassing only exist with the `mask` feature).
+    unsafe {
+        env::set_var("RUSTFLAGS", "--target x86_64-unknown-linux-musl");
+    }
+    expand_args(
+        "tests/dispatching-x86/cached-mask.rs",
+        &["--features", "dispatch,detect-features,mask"],
+    );
 }
@@ -11,7 +11,7 @@ fn sample() {
                             target_arch = "arm"
                         )
                     )
-                )] { "fallback" }
+                )] { { #[cfg(not(any(any(), any())))] { "fallback" } } }
             }
         }
     };
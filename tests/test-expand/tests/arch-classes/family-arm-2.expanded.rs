@@ -13,7 +13,7 @@ fn sample() {
                             target_arch = "csky"
                         )
                     )
-                )] { "fallback" }
+                )] { { #[cfg(not(any(any(), any())))] { "fallback" } } }
             }
         }
     };
@@ -12,7 +12,7 @@ fn sample() {
                             target_arch = "mips64r6"
                         )
                     )
-                )] { "fallback" }
+                )] { { #[cfg(not(any(any(), any())))] { "fallback" } } }
             }
         }
     };
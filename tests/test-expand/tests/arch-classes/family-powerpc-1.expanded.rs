@@ -4,7 +4,7 @@ fn sample() {
         {
             {
                 #[cfg(not(any(target_arch = "powerpc", target_arch = "powerpc64")))]
-                { "fallback" }
+                { { #[cfg(not(any(any(), any())))] { "fallback" } } }
             }
         }
     };
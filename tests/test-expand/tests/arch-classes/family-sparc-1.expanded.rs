@@ -4,7 +4,7 @@ fn sample() {
         {
             {
                 #[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
-                { "fallback" }
+                { { #[cfg(not(any(any(), any())))] { "fallback" } } }
             }
         }
     };
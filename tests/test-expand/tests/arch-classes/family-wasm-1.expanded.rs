@@ -4,7 +4,7 @@ fn sample() {
         {
             {
                 #[cfg(not(any(target_arch = "wasm32", target_arch = "wasm64")))]
-                { "fallback" }
+                { { #[cfg(not(any(any(), any())))] { "fallback" } } }
             }
         }
     };
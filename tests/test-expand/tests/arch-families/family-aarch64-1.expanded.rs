@@ -4,7 +4,7 @@ fn sample() {
         {
             {
                 #[cfg(not(any(target_arch = "aarch64", target_arch = "arm64ec")))]
-                { "fallback" }
+                { { #[cfg(not(any(any(), any())))] { "fallback" } } }
             }
         }
     };
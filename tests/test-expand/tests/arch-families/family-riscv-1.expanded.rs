@@ -4,7 +4,7 @@ fn sample() {
         {
             {
                 #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
-                { "fallback" }
+                { { #[cfg(not(any(any(), any())))] { "fallback" } } }
             }
         }
     };
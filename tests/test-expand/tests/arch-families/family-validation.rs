@@ -0,0 +1,21 @@
+fn sample() {
+    // Features of clauses not configured on the target are also validated.
+    let value = target_feature_dispatch::target_feature_dispatch! {
+        #[cfg_attr(feature = "detect-features", dynamic)]
+        #[cfg_attr(feature = "unstable", unstable)]
+        #[cfg_non_fallback(feature = "dispatch")]
+        if family("x86") {
+            if "avx2" {
+                "x86 + AVX2"
+            } else if "sse2" {
+                "x86 + SSE2"
+            }
+        } else if family("aarch64") {
+            if "sve2" {
+                "AArch64 + SVE2"
+            }
+        } else {
+            "fallback"
+        }
+    };
+}
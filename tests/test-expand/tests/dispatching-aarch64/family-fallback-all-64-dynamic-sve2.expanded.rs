@@ -6,15 +6,39 @@ fn sample() {
                 #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec"))]
                 {
                     {
-                        #[cfg(any(any(), feature = "detect-features"))]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"aarch64\")",
+                            &[
+                                &[::target_feature_dispatch::__private::features::AARCH64],
+                            ],
+                            "sve2",
+                        );
                         {
-                            if {
-                                true || ::std_detect::detect::__is_feature_detected::sve2()
-                            } {
-                                "AArch64 + SVE2"
-                            } else {
-                                {
-                                    #[cfg(all(target_feature = "sve2"))] { "AArch64 + SVE2" }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    true || ::std_detect::detect::__is_feature_detected::sve2()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "AArch64 + SVE2" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(all(target_feature = "sve2"))]
+                                        {
+                                            {
+                                                #[cfg(not(any(any(), any())))]
+                                                { { #[cfg(not(any()))] { "AArch64 + SVE2" } } }
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
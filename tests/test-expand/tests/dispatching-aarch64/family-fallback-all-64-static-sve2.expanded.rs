@@ -6,8 +6,32 @@ fn sample() {
                 #[cfg(any(target_arch = "aarch64", target_arch = "arm64ec"))]
                 {
                     {
-                        #[cfg(not(any(any(), feature = "detect-features")))]
-                        { { #[cfg(all(target_feature = "sve2"))] { "AArch64 + SVE2" } } }
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"aarch64\")",
+                            &[
+                                &[::target_feature_dispatch::__private::features::AARCH64],
+                            ],
+                            "sve2",
+                        );
+                        {
+                            #[cfg(
+                                all(
+                                    not(any(any(), feature = "detect-features")),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                {
+                                    #[cfg(all(target_feature = "sve2"))]
+                                    {
+                                        {
+                                            #[cfg(not(any(any(), any())))]
+                                            { { #[cfg(not(any()))] { "AArch64 + SVE2" } } }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
             }
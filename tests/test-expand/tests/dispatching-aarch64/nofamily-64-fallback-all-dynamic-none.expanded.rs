@@ -6,14 +6,35 @@ fn sample() {
                 #[cfg(any(target_arch = "aarch64"))]
                 {
                     {
-                        #[cfg(any(any(), feature = "detect-features"))]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"aarch64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::AARCH64],
+                            ],
+                            "sve2",
+                        );
                         {
-                            if {
-                                false || ::std_detect::detect::__is_feature_detected::sve2()
-                            } {
-                                "AArch64 + SVE2"
-                            } else {
-                                { #[cfg(not(all(target_feature = "sve2")))] { "fallback" } }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    false || ::std_detect::detect::__is_feature_detected::sve2()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "AArch64 + SVE2" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(not(all(target_feature = "sve2")))]
+                                        { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                    }
+                                }
                             }
                         }
                     }
@@ -6,8 +6,27 @@ fn sample() {
                 #[cfg(any(target_arch = "aarch64"))]
                 {
                     {
-                        #[cfg(not(any(any(), feature = "detect-features")))]
-                        { { #[cfg(not(all(target_feature = "sve2")))] { "fallback" } } }
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"aarch64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::AARCH64],
+                            ],
+                            "sve2",
+                        );
+                        {
+                            #[cfg(
+                                all(
+                                    not(any(any(), feature = "detect-features")),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                {
+                                    #[cfg(not(all(target_feature = "sve2")))]
+                                    { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                }
+                            }
+                        }
                     }
                 }
             }
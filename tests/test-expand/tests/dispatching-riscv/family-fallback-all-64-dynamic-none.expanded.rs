@@ -6,14 +6,33 @@ fn sample() {
                 #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
                 {
                     {
-                        #[cfg(any(any(), feature = "detect-features"))]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "zba",
+                        );
                         {
-                            if {
-                                false || ::std_detect::detect::__is_feature_detected::zba()
-                            } {
-                                "RISC-V + Zba"
-                            } else {
-                                { #[cfg(not(all(target_feature = "zba")))] { "fallback" } }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    false || ::std_detect::detect::__is_feature_detected::zba()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "RISC-V + Zba" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(not(all(target_feature = "zba")))]
+                                        { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                    }
+                                }
                             }
                         }
                     }
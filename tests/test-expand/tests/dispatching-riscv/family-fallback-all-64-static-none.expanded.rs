@@ -6,8 +6,25 @@ fn sample() {
                 #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
                 {
                     {
-                        #[cfg(not(any(any(), feature = "detect-features")))]
-                        { { #[cfg(not(all(target_feature = "zba")))] { "fallback" } } }
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "zba",
+                        );
+                        {
+                            #[cfg(
+                                all(
+                                    not(any(any(), feature = "detect-features")),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                {
+                                    #[cfg(not(all(target_feature = "zba")))]
+                                    { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -4,7 +4,27 @@ fn sample() {
         {
             {
                 #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
-                { { #[cfg(not(all(target_feature = "zba")))] { "fallback" } } }
+                {
+                    {
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"riscv32\" || \"riscv64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::RISCV],
+                                &[::target_feature_dispatch::__private::features::RISCV],
+                            ],
+                            "zba",
+                        );
+                        {
+                            #[cfg(not(any()))]
+                            {
+                                {
+                                    #[cfg(not(all(target_feature = "zba")))]
+                                    { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
     };
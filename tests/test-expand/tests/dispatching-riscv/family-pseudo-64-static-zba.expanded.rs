@@ -4,7 +4,32 @@ fn sample() {
         {
             {
                 #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
-                { { #[cfg(all(target_feature = "zba"))] { "RISC-V + Zba" } } }
+                {
+                    {
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"riscv32\" || \"riscv64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::RISCV],
+                                &[::target_feature_dispatch::__private::features::RISCV],
+                            ],
+                            "zba",
+                        );
+                        {
+                            #[cfg(not(any()))]
+                            {
+                                {
+                                    #[cfg(all(target_feature = "zba"))]
+                                    {
+                                        {
+                                            #[cfg(not(any(any(), any())))]
+                                            { { #[cfg(not(any()))] { "RISC-V + Zba" } } }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
     };
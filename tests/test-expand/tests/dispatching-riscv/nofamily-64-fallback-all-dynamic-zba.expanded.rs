@@ -6,14 +6,38 @@ fn sample() {
                 #[cfg(any(target_arch = "riscv64"))]
                 {
                     {
-                        #[cfg(any(any(), feature = "detect-features"))]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"riscv64\"",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "zba",
+                        );
                         {
-                            if {
-                                true || ::std_detect::detect::__is_feature_detected::zba()
-                            } {
-                                "RISC-V + Zba"
-                            } else {
-                                { #[cfg(all(target_feature = "zba"))] { "RISC-V + Zba" } }
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    true || ::std_detect::detect::__is_feature_detected::zba()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "RISC-V + Zba" } } }
+                                    }
+                                } else {
+                                    {
+                                        #[cfg(all(target_feature = "zba"))]
+                                        {
+                                            {
+                                                #[cfg(not(any(any(), any())))]
+                                                { { #[cfg(not(any()))] { "RISC-V + Zba" } } }
+                                            }
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -6,8 +6,30 @@ fn sample() {
                 #[cfg(any(target_arch = "riscv64"))]
                 {
                     {
-                        #[cfg(not(any(any(), feature = "detect-features")))]
-                        { { #[cfg(all(target_feature = "zba"))] { "RISC-V + Zba" } } }
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"riscv64\"",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "zba",
+                        );
+                        {
+                            #[cfg(
+                                all(
+                                    not(any(any(), feature = "detect-features")),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                {
+                                    #[cfg(all(target_feature = "zba"))]
+                                    {
+                                        {
+                                            #[cfg(not(any(any(), any())))]
+                                            { { #[cfg(not(any()))] { "RISC-V + Zba" } } }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
             }
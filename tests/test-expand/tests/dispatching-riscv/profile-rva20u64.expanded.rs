@@ -0,0 +1,108 @@
+fn sample() {
+    let value = {
+        #[cfg(all(all(), feature = "dispatch"))]
+        {
+            {
+                #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+                {
+                    {
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "za128rs",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "ziccamoa",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "ziccif",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "zicclsm",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "ziccrse",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "a",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "c",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "d",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "f",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "m",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "zicntr",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"riscv\")",
+                            &[&[::target_feature_dispatch::__private::features::RISCV]],
+                            "zicsr",
+                        );
+                        {
+                            #[cfg(
+                                all(
+                                    not(any(any(), feature = "detect-features")),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                {
+                                    #[cfg(
+                                        not(
+                                            all(
+                                                target_arch = "riscv64",
+                                                target_feature = "a",
+                                                target_feature = "c",
+                                                target_feature = "d",
+                                                target_feature = "f",
+                                                target_feature = "m",
+                                                target_feature = "za128rs",
+                                                target_feature = "ziccamoa",
+                                                target_feature = "ziccif",
+                                                target_feature = "zicclsm",
+                                                target_feature = "ziccrse",
+                                                target_feature = "zicntr",
+                                                target_feature = "zicsr"
+                                            )
+                                        )
+                                    )]
+                                    {
+                                        { #[cfg(not(any(any(), any())))] { "RISC-V (fallback)" } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,15 @@
+fn sample() {
+    let value = target_feature_dispatch::target_feature_dispatch! {
+        #[cfg_attr(feature = "detect-features", dynamic)]
+        #[cfg_non_fallback(feature = "dispatch")]
+        if family("riscv") {
+            if profile("rva20u64") {
+                "RISC-V (rva20u64)"
+            } else {
+                "RISC-V (fallback)"
+            }
+        } else {
+            "fallback"
+        }
+    };
+}
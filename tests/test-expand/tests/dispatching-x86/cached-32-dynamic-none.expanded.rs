@@ -0,0 +1,98 @@
+fn sample() {
+    let value = {
+        #[cfg(all(all(), feature = "dispatch"))]
+        {
+            {
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                {
+                    {
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"x86\")",
+                            &[&[::target_feature_dispatch::__private::features::X86]],
+                            "avx2",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"x86\")",
+                            &[&[::target_feature_dispatch::__private::features::X86]],
+                            "sse2",
+                        );
+                        const _: () = {
+                            struct UnreachableClause<const UNREACHABLE: bool>;
+                            impl UnreachableClause<true> {
+                                #[deprecated = "feature-specific clause `if \"sse2\"` is unreachable because the preceding clause `if \"avx2\"` always matches first"]
+                                const fn check() {}
+                            }
+                            impl UnreachableClause<false> {
+                                const fn check() {}
+                            }
+                            UnreachableClause::<
+                                {
+                                    ::target_feature_dispatch::__private::implications::implies(
+                                        &[
+                                            &[::target_feature_dispatch::__private::implications::X86],
+                                        ],
+                                        &[&[&["sse2"]]],
+                                        &[&[&["avx2"]]],
+                                    )
+                                },
+                            >::check()
+                        };
+                        {
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    any(any(), feature = "detect-features"),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                {
+                                    static __TGTFEAT_DISPATCH_CACHE: ::core::sync::atomic::AtomicU8 = ::core::sync::atomic::AtomicU8::new(
+                                        0,
+                                    );
+                                    let mut __tgtfeat_dispatch_index = __TGTFEAT_DISPATCH_CACHE
+                                        .load(::core::sync::atomic::Ordering::Relaxed);
+                                    if __tgtfeat_dispatch_index == 0 {
+                                        __tgtfeat_dispatch_index = if {
+                                            false || ::std_detect::detect::__is_feature_detected::avx2()
+                                        } {
+                                            1u8
+                                        } else {
+                                            if {
+                                                false || ::std_detect::detect::__is_feature_detected::sse2()
+                                            } {
+                                                1u8 + 1u8
+                                            } else {
+                                                1u8 + 1u8 + 1u8
+                                            }
+                                        };
+                                        __TGTFEAT_DISPATCH_CACHE
+                                            .store(
+                                                __tgtfeat_dispatch_index,
+                                                ::core::sync::atomic::Ordering::Relaxed,
+                                            );
+                                    }
+                                    if __tgtfeat_dispatch_index == 1u8 {
+                                        {
+                                            #[cfg(not(any(any(), any())))]
+                                            { { #[cfg(not(any()))] { "x86 + AVX2" } } }
+                                        }
+                                    } else {
+                                        if __tgtfeat_dispatch_index == 1u8 + 1u8 {
+                                            {
+                                                #[cfg(not(any(any(), any())))]
+                                                { { #[cfg(not(any()))] { "x86 + SSE2" } } }
+                                            }
+                                        } else {
+                                            { #[cfg(not(any(any(), any())))] { "x86 (fallback)" } }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
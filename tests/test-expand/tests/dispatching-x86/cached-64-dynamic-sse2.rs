@@ -0,0 +1 @@
+cached.rs
\ No newline at end of file
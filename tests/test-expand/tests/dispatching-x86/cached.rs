@@ -0,0 +1,18 @@
+fn sample() {
+    let value = target_feature_dispatch::target_feature_dispatch! {
+        #[cfg_attr(feature = "detect-features", cached)]
+        #[cfg_attr(feature = "unstable", unstable)]
+        #[cfg_non_fallback(feature = "dispatch")]
+        if family("x86") {
+            if "avx2" {
+                "x86 + AVX2"
+            } else if "sse2" {
+                "x86 + SSE2"
+            } else {
+                "x86 (fallback)"
+            }
+        } else {
+            "fallback"
+        }
+    };
+}
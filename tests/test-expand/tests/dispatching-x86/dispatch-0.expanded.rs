@@ -1,3 +1,6 @@
 fn sample() {
-    let value = { #[cfg(not(all(all(), feature = "dispatch")))] { "fallback" } };
+    let value = {
+        #[cfg(not(all(all(), feature = "dispatch")))]
+        { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+    };
 }
@@ -6,12 +6,53 @@ fn sample() {
                 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
                 {
                     {
-                        #[cfg(not(any(any(), feature = "detect-features")))]
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"x86\")",
+                            &[&[::target_feature_dispatch::__private::features::X86]],
+                            "avx2",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"x86\")",
+                            &[&[::target_feature_dispatch::__private::features::X86]],
+                            "sse2",
+                        );
+                        const _: () = {
+                            struct UnreachableClause<const UNREACHABLE: bool>;
+                            impl UnreachableClause<true> {
+                                #[deprecated = "feature-specific clause `if \"sse2\"` is unreachable because the preceding clause `if \"avx2\"` always matches first"]
+                                const fn check() {}
+                            }
+                            impl UnreachableClause<false> {
+                                const fn check() {}
+                            }
+                            UnreachableClause::<
+                                {
+                                    ::target_feature_dispatch::__private::implications::implies(
+                                        &[
+                                            &[::target_feature_dispatch::__private::implications::X86],
+                                        ],
+                                        &[&[&["sse2"]]],
+                                        &[&[&["avx2"]]],
+                                    )
+                                },
+                            >::check()
+                        };
                         {
+                            #[cfg(
+                                all(
+                                    not(any(any(), feature = "detect-features")),
+                                    not(any())
+                                )
+                            )]
                             {
-                                #[cfg(not(all(target_feature = "avx2")))]
                                 {
-                                    { #[cfg(not(all(target_feature = "sse2")))] { "fallback" } }
+                                    #[cfg(not(all(target_feature = "avx2")))]
+                                    {
+                                        {
+                                            #[cfg(not(all(target_feature = "sse2")))]
+                                            { { #[cfg(not(any(any(), any())))] { "fallback" } } }
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -6,21 +6,74 @@ fn sample() {
                 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
                 {
                     {
-                        #[cfg(any(any(), feature = "detect-features"))]
-                        {
-                            if {
-                                false || ::std_detect::detect::__is_feature_detected::avx2()
-                            } {
-                                "x86 + AVX2"
-                            } else if {
-                                true || ::std_detect::detect::__is_feature_detected::sse2()
-                            } {
-                                "x86 + SSE2"
-                            } else {
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"x86\")",
+                            &[&[::target_feature_dispatch::__private::features::X86]],
+                            "avx2",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "family(\"x86\")",
+                            &[&[::target_feature_dispatch::__private::features::X86]],
+                            "sse2",
+                        );
+                        const _: () = {
+                            struct UnreachableClause<const UNREACHABLE: bool>;
+                            impl UnreachableClause<true> {
+                                #[deprecated = "feature-specific clause `if \"sse2\"` is unreachable because the preceding clause `if \"avx2\"` always matches first"]
+                                const fn check() {}
+                            }
+                            impl UnreachableClause<false> {
+                                const fn check() {}
+                            }
+                            UnreachableClause::<
                                 {
-                                    #[cfg(not(all(target_feature = "avx2")))]
+                                    ::target_feature_dispatch::__private::implications::implies(
+                                        &[
+                                            &[::target_feature_dispatch::__private::implications::X86],
+                                        ],
+                                        &[&[&["sse2"]]],
+                                        &[&[&["avx2"]]],
+                                    )
+                                },
+                            >::check()
+                        };
+                        {
+                            #[cfg(
+                                all(
+                                    any(any(), feature = "detect-features"),
+                                    not(all(any(), not(feature = "detect-features"))),
+                                    not(any())
+                                )
+                            )]
+                            {
+                                if {
+                                    false || ::std_detect::detect::__is_feature_detected::avx2()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "x86 + AVX2" } } }
+                                    }
+                                } else if {
+                                    true || ::std_detect::detect::__is_feature_detected::sse2()
+                                } {
+                                    {
+                                        #[cfg(not(any(any(), any())))]
+                                        { { #[cfg(not(any()))] { "x86 + SSE2" } } }
+                                    }
+                                } else {
                                     {
-                                        { #[cfg(all(target_feature = "sse2"))] { "x86 + SSE2" } }
+                                        #[cfg(not(all(target_feature = "avx2")))]
+                                        {
+                                            {
+                                                #[cfg(all(target_feature = "sse2"))]
+                                                {
+                                                    {
+                                                        #[cfg(not(any(any(), any())))]
+                                                        { { #[cfg(not(any()))] { "x86 + SSE2" } } }
+                                                    }
+                                                }
+                                            }
+                                        }
                                     }
                                 }
                             }
@@ -6,8 +6,63 @@ fn sample() {
                 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
                 {
                     {
-                        #[cfg(not(all(target_feature = "avx2")))]
-                        { { #[cfg(all(target_feature = "sse2"))] { "x86 + SSE2" } } }
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"x86\" || \"x86_64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::X86],
+                                &[::target_feature_dispatch::__private::features::X86],
+                            ],
+                            "avx2",
+                        );
+                        const _: () = ::target_feature_dispatch::__private::features::check(
+                            "\"x86\" || \"x86_64\"",
+                            &[
+                                &[::target_feature_dispatch::__private::features::X86],
+                                &[::target_feature_dispatch::__private::features::X86],
+                            ],
+                            "sse2",
+                        );
+                        const _: () = {
+                            struct UnreachableClause<const UNREACHABLE: bool>;
+                            impl UnreachableClause<true> {
+                                #[deprecated = "feature-specific clause `if \"sse2\"` is unreachable because the preceding clause `if \"avx2\"` always matches first"]
+                                const fn check() {}
+                            }
+                            impl UnreachableClause<false> {
+                                const fn check() {}
+                            }
+                            UnreachableClause::<
+                                {
+                                    ::target_feature_dispatch::__private::implications::implies(
+                                        &[
+                                            &[::target_feature_dispatch::__private::implications::X86],
+                                            &[::target_feature_dispatch::__private::implications::X86],
+                                        ],
+                                        &[&[&["sse2"]]],
+                                        &[&[&["avx2"]]],
+                                    )
+                                },
+                            >::check()
+                        };
+                        {
+                            #[cfg(not(any()))]
+                            {
+                                {
+                                    #[cfg(not(all(target_feature = "avx2")))]
+                                    {
+                                        {
+                                            #[cfg(all(target_feature = "sse2"))]
+                                            {
+                                                {
+                                                    #[cfg(not(any(any(), any())))]
+                                                    { { #[cfg(not(any()))] { "x86 + SSE2" } } }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -133,6 +133,60 @@ fn test_x86_features() {
     }
 }
 
+#[test]
+fn test_x86_features_cached() {
+    // Evaluate multiple times to test both the first (detecting) run and
+    // succeeding (cached) runs.
+    for _ in 0..2 {
+        let is_sse2: Option<bool> = target_feature_dispatch! {
+            #[cfg_non_fallback(feature = "dispatch")]
+            #[cfg_attr(feature = "detect-features", cached)]
+            #[cfg_attr(feature = "unstable", unstable)]
+            if family("x86") {
+                if "sse2" {
+                    Some(true)
+                } else {
+                    Some(false)
+                }
+            } else {
+                None
+            }
+        };
+        let is_avx2: Option<bool> = target_feature_dispatch! {
+            #[cfg_non_fallback(feature = "dispatch")]
+            #[cfg_attr(feature = "detect-features", cached)]
+            #[cfg_attr(feature = "unstable", unstable)]
+            if family("x86") {
+                if "avx2" {
+                    Some(true)
+                } else {
+                    Some(false)
+                }
+            } else {
+                None
+            }
+        };
+        // Test feature detection results
+        if cfg!(feature = "dispatch") && cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+            // SSE2
+            if cfg!(feature = "target-x86-sse2") {
+                assert_eq!(is_sse2, Some(true), "SSE2 must be available.");
+            } else {
+                assert_eq!(is_sse2, Some(false), "SSE2 must NOT be available.");
+            }
+            // AVX2
+            if cfg!(feature = "target-x86-avx2") {
+                assert_eq!(is_avx2, Some(true), "AVX2 must be available.");
+            } else {
+                assert_eq!(is_avx2, Some(false), "AVX2 must NOT be available.");
+            }
+        } else {
+            assert!(is_sse2.is_none(), "Fallback must be working.");
+            assert!(is_avx2.is_none(), "Fallback must be working.");
+        }
+    }
+}
+
 #[test]
 fn test_wasm_features() {
     let is_simd128: Option<bool> = target_feature_dispatch! {
@@ -160,3 +214,50 @@ fn test_wasm_features() {
         assert!(is_simd128.is_none(), "Fallback must be working.");
     }
 }
+
+#[test]
+fn test_vector_lengths() {
+    let is_vlen_256: Option<bool> = target_feature_dispatch! {
+        #[cfg_non_fallback(feature = "dispatch")]
+        #[cfg_attr(feature = "detect-vlen", dynamic)]
+        if family("riscv") {
+            if vlen(>= 256) {
+                Some(true)
+            } else {
+                Some(false)
+            }
+        } else if family("aarch64") {
+            if sve_vl(>= 256) {
+                Some(true)
+            } else {
+                Some(false)
+            }
+        } else {
+            None
+        }
+    };
+    // Test vector length detection results
+    if cfg!(feature = "dispatch")
+        && cfg!(any(
+            target_arch = "riscv32",
+            target_arch = "riscv64",
+            target_arch = "aarch64"
+        ))
+    {
+        if cfg!(feature = "target-vlen-256") {
+            assert_eq!(
+                is_vlen_256,
+                Some(true),
+                "Vector length must be 256 bits or longer."
+            );
+        } else {
+            assert_eq!(
+                is_vlen_256,
+                Some(false),
+                "Vector length must be shorter than 256 bits."
+            );
+        }
+    } else {
+        assert!(is_vlen_256.is_none(), "Fallback must be working.");
+    }
+}